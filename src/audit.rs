@@ -0,0 +1,144 @@
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Environment variable naming the file that mutating API calls are appended to.
+pub const LOG_FILE_VAR: &str = "TRELLO_LOG_FILE";
+
+/// Appends one JSON line per mutating API call to an audit log file.
+/// When no file is configured, logging is a no-op.
+#[derive(Debug, Default)]
+pub struct AuditLogger {
+    file: Option<File>,
+}
+
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    timestamp: String,
+    method: &'a str,
+    path: String,
+    status: u16,
+}
+
+impl AuditLogger {
+    /// A logger that discards all entries.
+    pub fn disabled() -> Self {
+        Self { file: None }
+    }
+
+    /// Open the file named by `TRELLO_LOG_FILE` in append mode, if set.
+    pub fn from_env() -> Result<Self> {
+        match env::var(LOG_FILE_VAR) {
+            Ok(path) if !path.is_empty() => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open audit log file {}", path))?;
+                Ok(Self { file: Some(file) })
+            }
+            _ => Ok(Self::disabled()),
+        }
+    }
+
+    pub fn log(&mut self, method: &str, path: &str, status: u16) -> Result<()> {
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+
+        let entry = AuditEntry {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            method,
+            path: strip_credentials(path),
+            status,
+        };
+        let line = serde_json::to_string(&entry).context("Failed to serialize audit entry")?;
+        writeln!(file, "{}", line).context("Failed to write audit log entry")
+    }
+}
+
+/// Remove `key` and `token` query parameters so credentials never reach the log.
+pub fn strip_credentials(path: &str) -> String {
+    let Some((base, query)) = path.split_once('?') else {
+        return path.to_string();
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|param| {
+            let name = param.split('=').next().unwrap_or_default();
+            name != "key" && name != "token"
+        })
+        .collect();
+
+    if kept.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, kept.join("&"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn strip_credentials_removes_key_and_token() {
+        assert_eq!(
+            strip_credentials("/cards/123?key=secret_key&token=secret_token"),
+            "/cards/123"
+        );
+        assert_eq!(
+            strip_credentials("/cards/123?fields=name&key=secret_key&token=secret_token"),
+            "/cards/123?fields=name"
+        );
+        assert_eq!(
+            strip_credentials("/cards/123?token=secret_token&pos=top"),
+            "/cards/123?pos=top"
+        );
+        assert_eq!(strip_credentials("/cards/123"), "/cards/123");
+    }
+
+    #[test]
+    fn log_appends_json_lines_without_credentials() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .unwrap();
+        let mut logger = AuditLogger { file: Some(file) };
+
+        logger
+            .log("PUT", "/cards/123?key=secret_key&token=secret_token", 200)
+            .unwrap();
+        logger.log("DELETE", "/cards/456", 404).unwrap();
+
+        let contents = fs::read_to_string(&log_path).unwrap();
+        assert!(!contents.contains("secret"), "Contents: {}", contents);
+
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["method"], "PUT");
+        assert_eq!(lines[0]["path"], "/cards/123");
+        assert_eq!(lines[0]["status"], 200);
+        assert!(lines[0]["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(lines[1]["method"], "DELETE");
+        assert_eq!(lines[1]["status"], 404);
+    }
+
+    #[test]
+    fn disabled_logger_is_a_no_op() {
+        let mut logger = AuditLogger::disabled();
+        logger.log("POST", "/cards", 200).unwrap();
+    }
+}
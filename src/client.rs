@@ -1,13 +1,23 @@
 #![allow(dead_code)] // Methods will be used as features are added
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
+use reqwest::Url;
+use reqwest::blocking::{Client, Request, RequestBuilder};
 use serde::{Serialize, de::DeserializeOwned};
 
+use crate::audit::AuditLogger;
 use crate::config::Config;
+use crate::error::TrelloError;
 use crate::models::{
-    Action, AddComment, AddLabel, ArchiveCard, Board, Card, CreateCard, Label, List,
-    UpdateCardDesc, UpdateCardPosition, UpdateListPosition,
+    Action, ActionRef, AddCheckItem, AddComment, AddLabel, AddMember, ArchiveCard, ArchiveList,
+    Board, BoardMember, BoardWithDetails, Card, CardCover, Checklist, CopyCard, CreateCard,
+    CreateChecklist, CreateList, InviteMember, Label, List, Member, MoveCardToList, Organization,
+    UpdateBoardClosed, UpdateCardCover, UpdateCardDesc, UpdateCardName, UpdateCardPosition,
+    UpdateCardStart, UpdateListPosition, VoteOnCard, Webhook,
 };
 
 const BASE_URL: &str = "https://api.trello.com/1";
@@ -16,6 +26,29 @@ pub struct TrelloClient {
     client: Client,
     api_key: String,
     api_token: String,
+    cache: Option<RefCell<RequestCache>>,
+    audit: RefCell<AuditLogger>,
+}
+
+/// In-memory cache of GET response bodies, keyed by URL (without credentials).
+/// Lives only as long as the client, i.e. a single CLI invocation.
+#[derive(Debug, Default)]
+pub struct RequestCache {
+    entries: HashMap<String, String>,
+}
+
+impl RequestCache {
+    fn get(&self, url: &str) -> Option<&str> {
+        self.entries.get(url).map(String::as_str)
+    }
+
+    fn insert(&mut self, url: String, body: String) {
+        self.entries.insert(url, body);
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +62,40 @@ fn looks_like_id(input: &str) -> bool {
     input.len() == 24 && input.chars().all(|c| c.is_ascii_hexdigit())
 }
 
+/// Call `fetch_page` with each page's cursor until a page comes back empty or with
+/// fewer than `limit` items, the last item has no cursor, or `max_items` items have
+/// been collected.
+fn collect_pages<T>(
+    mut fetch_page: impl FnMut(Option<&str>) -> Result<Vec<T>>,
+    get_cursor: impl Fn(&T) -> Option<String>,
+    limit: usize,
+    max_items: usize,
+) -> Result<Vec<T>> {
+    let mut all_items = Vec::new();
+    let mut before: Option<String> = None;
+
+    loop {
+        let batch = fetch_page(before.as_deref())?;
+        let batch_size = batch.len();
+        if batch_size == 0 {
+            break;
+        }
+
+        before = batch.last().and_then(&get_cursor);
+        all_items.extend(batch);
+
+        // If we got fewer than limit, we've reached the end
+        if batch_size < limit || before.is_none() || all_items.len() >= max_items {
+            break;
+        }
+    }
+
+    Ok(all_items)
+}
+
+/// The most actions Trello returns in one page.
+const MAX_ACTIONS_PER_PAGE: usize = 1000;
+
 pub fn find_unique_match(items: &[NamedItem], query: &str) -> Result<String> {
     let query_lower = query.to_lowercase();
     let matches: Vec<&NamedItem> = items
@@ -37,7 +104,7 @@ pub fn find_unique_match(items: &[NamedItem], query: &str) -> Result<String> {
         .collect();
 
     match matches.len() {
-        0 => anyhow::bail!("No matches found for '{}'", query),
+        0 => Err(TrelloError::NotFound(format!("No matches found for '{}'", query)).into()),
         1 => Ok(matches[0].id.clone()),
         _ => {
             let options = matches
@@ -45,11 +112,11 @@ pub fn find_unique_match(items: &[NamedItem], query: &str) -> Result<String> {
                 .map(|item| format!("{} (board: {})", item.name, item.context))
                 .collect::<Vec<_>>()
                 .join(", ");
-            anyhow::bail!(
+            Err(TrelloError::User(format!(
                 "Multiple matches found for '{}': {}. Use -b/--board to disambiguate.",
-                query,
-                options
-            )
+                query, options
+            ))
+            .into())
         }
     }
 }
@@ -66,12 +133,61 @@ pub fn compute_position(cards: &[Card], target_pos: usize) -> String {
     }
 }
 
+/// Position for a new card or list directly after (or before) the one at `positions[index]`,
+/// where `positions` is sorted: midway to its neighbour, or the end if it has none.
+pub fn position_next_to(positions: &[f64], index: usize, after: bool) -> String {
+    let reference = positions[index];
+    if after {
+        match positions.get(index + 1) {
+            Some(next) => (reference + (next - reference) / 2.0).to_string(),
+            None => "bottom".to_string(),
+        }
+    } else if index == 0 {
+        "top".to_string()
+    } else {
+        let prev = positions[index - 1];
+        (prev + (reference - prev) / 2.0).to_string()
+    }
+}
+
 impl TrelloClient {
     pub fn new(config: &Config) -> Self {
+        Self::with_cache(config, false)
+    }
+
+    /// Create a client that optionally caches GET responses for its lifetime.
+    /// Any mutating request clears the cache so later reads see the change.
+    pub fn with_cache(config: &Config, enable_cache: bool) -> Self {
         Self {
             client: Client::new(),
             api_key: config.api_key().to_string(),
             api_token: config.api_token().to_string(),
+            cache: enable_cache.then(|| RefCell::new(RequestCache::default())),
+            audit: RefCell::new(AuditLogger::disabled()),
+        }
+    }
+
+    /// Record every mutating request (PUT, POST, PATCH, DELETE) with the given logger.
+    pub fn with_audit_logger(mut self, logger: AuditLogger) -> Self {
+        self.audit = RefCell::new(logger);
+        self
+    }
+
+    /// Replace the HTTP client with one using the given request timeout.
+    /// `None` disables the timeout entirely.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Result<Self> {
+        self.client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(self)
+    }
+
+    /// Record a mutating request in the audit log. The request has already been
+    /// made, so a failure to write the log is reported as a warning, not an error.
+    fn audit(&self, method: &str, path: &str, status: reqwest::StatusCode) {
+        if let Err(e) = self.audit.borrow_mut().log(method, path, status.as_u16()) {
+            eprintln!("Warning: failed to write audit log: {:#}", e);
         }
     }
 
@@ -84,74 +200,183 @@ impl TrelloClient {
         format!("{}{}", BASE_URL, path)
     }
 
-    fn add_auth(&self, url: &str) -> String {
-        let separator = if url.contains('?') { '&' } else { '?' };
-        format!(
-            "{}{}key={}&token={}",
-            url, separator, self.api_key, self.api_token
-        )
+    fn add_auth(&self, request: RequestBuilder) -> RequestBuilder {
+        request.query(&[
+            ("key", self.api_key.as_str()),
+            ("token", self.api_token.as_str()),
+        ])
+    }
+
+    fn build_get_request(&self, path: &str, params: &[(&str, &str)]) -> Result<Request> {
+        self.add_auth(self.client.get(self.build_url(path)).query(params))
+            .build()
+            .context("Failed to build GET request")
+    }
+
+    fn build_patch_request<B: Serialize>(&self, path: &str, body: &B) -> Result<Request> {
+        self.add_auth(self.client.patch(self.build_url(path)))
+            .json(body)
+            .build()
+            .context("Failed to build PATCH request")
+    }
+
+    fn build_post_empty_request(&self, path: &str) -> Result<Request> {
+        self.add_auth(self.client.post(self.build_url(path)))
+            .header(reqwest::header::CONTENT_LENGTH, 0)
+            .build()
+            .context("Failed to build POST request")
+    }
+
+    /// Fail with the response body if the status isn't a success, for calls that
+    /// don't read the body otherwise.
+    fn ensure_success(response: reqwest::blocking::Response) -> Result<()> {
+        if !response.status().is_success() {
+            return Err(TrelloError::from_http_response(response).into());
+        }
+        Ok(())
     }
 
     fn handle_response<T: DeserializeOwned>(response: reqwest::blocking::Response) -> Result<T> {
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().unwrap_or_default();
-            anyhow::bail!("API request failed with status {}: {}", status, body);
+            return Err(TrelloError::from_http_response(response).into());
         }
 
         response.json().context("Failed to parse JSON response")
     }
 
+    fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.borrow_mut().clear();
+        }
+    }
+
     pub fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
-        let url = self.add_auth(&self.build_url(path));
+        self.get_with_query(path, &[])
+    }
+
+    /// GET `path` with `params` appended as URL-encoded query parameters.
+    pub fn get_with_query<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T> {
+        let cache_key = Url::parse_with_params(&self.build_url(path), params)
+            .context("Failed to build request URL")?
+            .to_string();
+        if let Some(cache) = &self.cache
+            && let Some(body) = cache.borrow().get(&cache_key)
+        {
+            return serde_json::from_str(body).context("Failed to parse JSON response");
+        }
+
+        let request = self.build_get_request(path, params)?;
         let response = self
             .client
-            .get(&url)
-            .send()
+            .execute(request)
             .context("Failed to send GET request")?;
 
-        Self::handle_response(response)
+        if !response.status().is_success() {
+            return Err(TrelloError::from_http_response(response).into());
+        }
+
+        let body = response.text().context("Failed to read response body")?;
+        let value = serde_json::from_str(&body).context("Failed to parse JSON response")?;
+        if let Some(cache) = &self.cache {
+            cache.borrow_mut().insert(cache_key, body);
+        }
+        Ok(value)
     }
 
     pub fn put<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
-        let url = self.add_auth(&self.build_url(path));
         let response = self
-            .client
-            .put(&url)
+            .add_auth(self.client.put(self.build_url(path)))
             .json(body)
             .send()
             .context("Failed to send PUT request")?;
 
-        Self::handle_response(response)
+        self.invalidate_cache();
+        let status = response.status();
+        let result = Self::handle_response(response);
+        self.audit("PUT", path, status);
+        result
     }
 
     pub fn post<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
-        let url = self.add_auth(&self.build_url(path));
         let response = self
-            .client
-            .post(&url)
+            .add_auth(self.client.post(self.build_url(path)))
             .json(body)
             .send()
             .context("Failed to send POST request")?;
 
-        Self::handle_response(response)
+        self.invalidate_cache();
+        let status = response.status();
+        let result = Self::handle_response(response);
+        self.audit("POST", path, status);
+        result
     }
 
-    pub fn delete(&self, path: &str) -> Result<()> {
-        let url = self.add_auth(&self.build_url(path));
+    /// PATCH `body` to `path`. The core Trello API only uses PUT, but some Power-Up
+    /// endpoints take partial updates this way.
+    pub fn patch<T: DeserializeOwned, B: Serialize>(&self, path: &str, body: &B) -> Result<T> {
+        let request = self.build_patch_request(path, body)?;
         let response = self
             .client
-            .delete(&url)
+            .execute(request)
+            .context("Failed to send PATCH request")?;
+
+        self.invalidate_cache();
+        let status = response.status();
+        let result = Self::handle_response(response);
+        self.audit("PATCH", path, status);
+        result
+    }
+
+    pub fn delete(&self, path: &str) -> Result<()> {
+        let response = self
+            .add_auth(self.client.delete(self.build_url(path)))
             .send()
             .context("Failed to send DELETE request")?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().unwrap_or_default();
-            anyhow::bail!("API request failed with status {}: {}", status, body);
-        }
+        self.invalidate_cache();
+        let status = response.status();
+        let result = Self::ensure_success(response);
+        self.audit("DELETE", path, status);
+        result
+    }
 
-        Ok(())
+    /// POST with no body, for action endpoints such as `/lists/<id>/archiveAllCards`.
+    pub fn post_empty<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self.send_post_empty(path)?;
+        let status = response.status();
+        let result = Self::handle_response(response);
+        self.audit("POST", path, status);
+        result
+    }
+
+    /// Like `post_empty`, but discards the response body.
+    pub fn post_empty_void(&self, path: &str) -> Result<()> {
+        let response = self.send_post_empty(path)?;
+        let status = response.status();
+        let result = Self::ensure_success(response);
+        self.audit("POST", path, status);
+        result
+    }
+
+    fn send_post_empty(&self, path: &str) -> Result<reqwest::blocking::Response> {
+        let request = self.build_post_empty_request(path)?;
+        let response = self
+            .client
+            .execute(request)
+            .context("Failed to send POST request")?;
+
+        self.invalidate_cache();
+        Ok(response)
+    }
+
+    // Member operations
+
+    pub fn get_current_member(&self) -> Result<Member> {
+        self.get("/members/me")
     }
 
     // Card operations
@@ -164,6 +389,14 @@ impl TrelloClient {
         self.put(&path, &body)
     }
 
+    pub fn update_card_name(&self, card_id: &str, name: &str) -> Result<Card> {
+        let path = format!("/cards/{}", card_id);
+        let body = UpdateCardName {
+            name: name.to_string(),
+        };
+        self.put(&path, &body)
+    }
+
     pub fn get_card(&self, card_id: &str) -> Result<Card> {
         let path = format!("/cards/{}", card_id);
         self.get(&path)
@@ -197,7 +430,9 @@ impl TrelloClient {
         let label = labels
             .iter()
             .find(|l| l.name.eq_ignore_ascii_case(label_name))
-            .ok_or_else(|| anyhow::anyhow!("Label '{}' not found on board", label_name))?;
+            .ok_or_else(|| {
+                TrelloError::NotFound(format!("Label '{}' not found on board", label_name))
+            })?;
 
         if !card.id_labels.contains(&label.id) {
             self.add_label_to_card(&card.id, &label.id)?;
@@ -221,7 +456,9 @@ impl TrelloClient {
         let label = labels
             .iter()
             .find(|l| l.name.eq_ignore_ascii_case(label_name))
-            .ok_or_else(|| anyhow::anyhow!("Label '{}' not found on board", label_name))?;
+            .ok_or_else(|| {
+                TrelloError::NotFound(format!("Label '{}' not found on board", label_name))
+            })?;
 
         if card.id_labels.contains(&label.id) {
             self.remove_label_from_card(&card.id, &label.id)?;
@@ -250,6 +487,33 @@ impl TrelloClient {
         Ok(())
     }
 
+    /// Set a card's cover color, or clear the cover when `color` is `None`.
+    pub fn set_card_cover(&self, card_id: &str, color: Option<&str>) -> Result<Card> {
+        let path = format!("/cards/{}", card_id);
+        let body = UpdateCardCover {
+            cover: CardCover {
+                color: color.map(str::to_string),
+            },
+        };
+        self.put(&path, &body)
+    }
+
+    /// Set the card's start date (ISO 8601), or clear it with `None`.
+    pub fn update_card_start(&self, card_id: &str, start: Option<&str>) -> Result<Card> {
+        let path = format!("/cards/{}", card_id);
+        let body = UpdateCardStart {
+            start: start.map(str::to_string),
+        };
+        self.put(&path, &body)
+    }
+
+    /// Archive a card by ID without fetching it first.
+    pub fn archive_card_by_id(&self, card_id: &str) -> Result<Card> {
+        let path = format!("/cards/{}", card_id);
+        let body = ArchiveCard { closed: true };
+        self.put(&path, &body)
+    }
+
     pub fn add_comment_to_card(&self, card_id: &str, text: &str) -> Result<Action> {
         let path = format!("/cards/{}/actions/comments", card_id);
         let body = AddComment {
@@ -258,10 +522,66 @@ impl TrelloClient {
         self.post(&path, &body)
     }
 
+    /// Add the current member's vote to a card.
+    pub fn vote_on_card(&self, card_id: &str) -> Result<()> {
+        let member = self.get_current_member()?;
+        let path = format!("/cards/{}/membersVoted", card_id);
+        let body = VoteOnCard { value: member.id };
+        self.post::<serde_json::Value, _>(&path, &body)?;
+        Ok(())
+    }
+
+    pub fn assign_member_to_card(&self, card_id: &str, member_id: &str) -> Result<()> {
+        let path = format!("/cards/{}/idMembers", card_id);
+        let body = AddMember {
+            value: member_id.to_string(),
+        };
+        self.post::<serde_json::Value, _>(&path, &body)?;
+        Ok(())
+    }
+
+    pub fn remove_member_from_card(&self, card_id: &str, member_id: &str) -> Result<()> {
+        let path = format!("/cards/{}/idMembers/{}", card_id, member_id);
+        self.delete(&path)
+    }
+
+    pub fn remove_vote_from_card(&self, card_id: &str, member_id: &str) -> Result<()> {
+        let path = format!("/cards/{}/membersVoted/{}", card_id, member_id);
+        self.delete(&path)
+    }
+
     pub fn create_card(&self, body: &CreateCard) -> Result<Card> {
         self.post("/cards", body)
     }
 
+    /// Copy a card, with everything attached to it, to the bottom of a list.
+    pub fn copy_card(&self, card_id: &str, list_id: &str) -> Result<Card> {
+        let body = CopyCard {
+            id_list: list_id.to_string(),
+            id_card_source: card_id.to_string(),
+            keep_from_source: "all".to_string(),
+            pos: "bottom".to_string(),
+        };
+        self.post("/cards", &body)
+    }
+
+    pub fn create_checklist(&self, card_id: &str, name: &str) -> Result<Checklist> {
+        let path = format!("/cards/{}/checklists", card_id);
+        let body = CreateChecklist {
+            name: name.to_string(),
+        };
+        self.post(&path, &body)
+    }
+
+    pub fn add_check_item(&self, checklist_id: &str, name: &str) -> Result<()> {
+        let path = format!("/checklists/{}/checkItems", checklist_id);
+        let body = AddCheckItem {
+            name: name.to_string(),
+        };
+        self.post::<serde_json::Value, _>(&path, &body)?;
+        Ok(())
+    }
+
     pub fn resolve_list(&self, list: &str, board_filter: Option<&str>) -> Result<String> {
         if looks_like_id(list) {
             Ok(list.to_string())
@@ -270,11 +590,9 @@ impl TrelloClient {
         }
     }
 
-    pub fn resolve_list_by_name(
-        &self,
-        list_query: &str,
-        board_filter: Option<&str>,
-    ) -> Result<String> {
+    /// Boards to search when resolving names: the board with the given ID, boards whose
+    /// name contains the filter, or all open boards when no filter is given.
+    fn boards_for_filter(&self, board_filter: Option<&str>) -> Result<Vec<Board>> {
         let boards =
             if let Some(filter) = board_filter {
                 if looks_like_id(filter) {
@@ -290,7 +608,11 @@ impl TrelloClient {
                         .collect();
 
                     if filtered.is_empty() {
-                        anyhow::bail!("No boards matching '{}' found", filter);
+                        return Err(TrelloError::NotFound(format!(
+                            "No boards matching '{}' found",
+                            filter
+                        ))
+                        .into());
                     }
 
                     filtered
@@ -300,14 +622,51 @@ impl TrelloClient {
             };
 
         if boards.is_empty() {
-            anyhow::bail!("No boards found");
+            return Err(TrelloError::NotFound("No boards found".to_string()).into());
         }
 
+        Ok(boards)
+    }
+
+    pub fn resolve_list_by_name(
+        &self,
+        list_query: &str,
+        board_filter: Option<&str>,
+    ) -> Result<String> {
+        self.resolve_list_by_name_in(list_query, board_filter, false)
+    }
+
+    /// Like `resolve_list`, but also matches archived lists by name.
+    pub fn resolve_list_including_closed(
+        &self,
+        list: &str,
+        board_filter: Option<&str>,
+    ) -> Result<String> {
+        if looks_like_id(list) {
+            Ok(list.to_string())
+        } else {
+            self.resolve_list_by_name_in(list, board_filter, true)
+        }
+    }
+
+    fn resolve_list_by_name_in(
+        &self,
+        list_query: &str,
+        board_filter: Option<&str>,
+        include_closed: bool,
+    ) -> Result<String> {
+        let boards = self.boards_for_filter(board_filter)?;
+
         let mut list_items = Vec::new();
         for board in &boards {
-            let lists = self
+            let mut lists = self
                 .get_board_lists(&board.id)
                 .with_context(|| format!("Failed to fetch lists for board '{}'", board.name))?;
+            if include_closed {
+                lists.extend(self.get_board_lists_closed(&board.id).with_context(|| {
+                    format!("Failed to fetch archived lists for board '{}'", board.name)
+                })?);
+            }
             for list in lists {
                 list_items.push(NamedItem {
                     id: list.id,
@@ -320,44 +679,115 @@ impl TrelloClient {
         find_unique_match(&list_items, list_query)
     }
 
+    pub fn resolve_card(&self, card: &str, board_filter: Option<&str>) -> Result<String> {
+        if looks_like_id(card) {
+            Ok(card.to_string())
+        } else {
+            self.resolve_card_by_name(card, board_filter)
+        }
+    }
+
+    pub fn resolve_card_by_name(
+        &self,
+        card_query: &str,
+        board_filter: Option<&str>,
+    ) -> Result<String> {
+        let boards = self.boards_for_filter(board_filter)?;
+
+        let mut card_items = Vec::new();
+        for board in &boards {
+            let cards = self
+                .get_board_cards(&board.id)
+                .with_context(|| format!("Failed to fetch cards for board '{}'", board.name))?;
+            for card in cards {
+                card_items.push(NamedItem {
+                    id: card.id,
+                    name: card.name,
+                    context: board.name.clone(),
+                });
+            }
+        }
+
+        find_unique_match(&card_items, card_query)
+    }
+
     pub fn get_list_cards(&self, list_id: &str) -> Result<Vec<Card>> {
         let path = format!("/lists/{}/cards", list_id);
         self.get(&path)
     }
 
-    pub fn get_card_comments(&self, card_id: &str) -> Result<Vec<Action>> {
-        let mut all_comments = Vec::new();
-        let limit = 1000;
-        let mut before: Option<String> = None;
-
-        loop {
-            let path = match &before {
-                Some(id) => format!(
-                    "/cards/{}/actions?filter=commentCard&limit={}&before={}",
-                    card_id, limit, id
-                ),
-                None => format!(
-                    "/cards/{}/actions?filter=commentCard&limit={}",
-                    card_id, limit
-                ),
-            };
+    /// Fetch cards in a list using a Trello card filter (`open`, `closed`, `all`).
+    pub fn get_list_cards_filtered(&self, list_id: &str, filter: &str) -> Result<Vec<Card>> {
+        let path = format!("/lists/{}/cards/{}", list_id, filter);
+        self.get(&path)
+    }
 
-            let batch: Vec<Action> = self.get(&path)?;
-            let batch_size = batch.len();
-            if batch_size == 0 {
-                break;
-            }
+    /// Fetch a card's checklists with just the names and states of their items.
+    pub fn get_card_checklists_brief(&self, card_id: &str) -> Result<Vec<Checklist>> {
+        let path = format!("/cards/{}/checklists", card_id);
+        self.get_with_query(
+            &path,
+            &[
+                ("fields", "name"),
+                ("checkItems", "all"),
+                ("checkItem_fields", "name,state"),
+            ],
+        )
+    }
 
-            before = batch.last().map(|a| a.id.clone());
-            all_comments.extend(batch);
+    pub fn get_card_comments(&self, card_id: &str) -> Result<Vec<Action>> {
+        let limit = MAX_ACTIONS_PER_PAGE;
+        let path = format!("/cards/{}/actions", card_id);
+        self.get_paginated(
+            &path,
+            &[("filter", "commentCard"), ("limit", &limit.to_string())],
+            |action: &Action| Some(action.id.clone()),
+            limit,
+        )
+    }
 
-            // If we got fewer than limit, we've reached the end
-            if batch_size < limit {
-                break;
-            }
-        }
+    pub fn get_action(&self, action_id: &str) -> Result<Action> {
+        let path = format!("/actions/{}", action_id);
+        self.get(&path)
+    }
 
-        Ok(all_comments)
+    /// Fetch every page of an endpoint that pages backwards with a `before` cursor.
+    ///
+    /// Each page is requested from `path` with `params`, which must ask for `limit`
+    /// items per page, plus `before` set to the cursor from the previous page.
+    /// `get_cursor` extracts the cursor from the last item of a page.
+    pub fn get_paginated<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+        get_cursor: impl Fn(&T) -> Option<String>,
+        limit: usize,
+    ) -> Result<Vec<T>> {
+        self.get_paginated_up_to(path, params, get_cursor, limit, usize::MAX)
+    }
+
+    /// Like `get_paginated`, but stop requesting pages once at least `max_items` items
+    /// have been fetched.
+    pub fn get_paginated_up_to<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+        get_cursor: impl Fn(&T) -> Option<String>,
+        limit: usize,
+        max_items: usize,
+    ) -> Result<Vec<T>> {
+        collect_pages(
+            |before| {
+                let mut page_params = params.to_vec();
+                if let Some(id) = before {
+                    page_params.push(("before", id));
+                }
+                self.get_with_query(path, &page_params)
+            },
+            get_cursor,
+            limit,
+            max_items,
+        )
     }
 
     pub fn move_card(&self, card_id: &str, position: &str) -> Result<Card> {
@@ -384,6 +814,17 @@ impl TrelloClient {
         self.put(&path, &body)
     }
 
+    /// Move a card to another list (on the same or a different board).
+    pub fn move_card_to_list(&self, card_id: &str, list: &List, position: &str) -> Result<Card> {
+        let path = format!("/cards/{}", card_id);
+        let body = MoveCardToList {
+            id_list: list.id.clone(),
+            id_board: list.id_board.clone(),
+            pos: position.to_string(),
+        };
+        self.put(&path, &body)
+    }
+
     // List operations
 
     pub fn get_list(&self, list_id: &str) -> Result<List> {
@@ -393,7 +834,49 @@ impl TrelloClient {
 
     pub fn get_board_lists(&self, board_id: &str) -> Result<Vec<List>> {
         let path = format!("/boards/{}/lists", board_id);
-        self.get(&path)
+        self.get_with_query(&path, &[("filter", "open")])
+    }
+
+    /// Create a list at the bottom of a board.
+    pub fn create_list(&self, board_id: &str, name: &str) -> Result<List> {
+        self.create_list_at(board_id, name, "bottom")
+    }
+
+    /// Create a list on a board at `pos` ("top", "bottom" or a numeric position).
+    pub fn create_list_at(&self, board_id: &str, name: &str, pos: &str) -> Result<List> {
+        let body = CreateList {
+            name: name.to_string(),
+            id_board: board_id.to_string(),
+            pos: pos.to_string(),
+        };
+        self.post("/lists", &body)
+    }
+
+    /// Fetch both open and archived lists on a board.
+    pub fn get_board_lists_all(&self, board_id: &str) -> Result<Vec<List>> {
+        let path = format!("/boards/{}/lists", board_id);
+        self.get_with_query(&path, &[("filter", "all")])
+    }
+
+    pub fn get_board_lists_closed(&self, board_id: &str) -> Result<Vec<List>> {
+        let path = format!("/boards/{}/lists", board_id);
+        self.get_with_query(&path, &[("filter", "closed")])
+    }
+
+    pub fn archive_list(&self, list_id: &str) -> Result<List> {
+        let path = format!("/lists/{}", list_id);
+        self.put(&path, &ArchiveList { closed: true })
+    }
+
+    pub fn restore_list(&self, list_id: &str) -> Result<List> {
+        let path = format!("/lists/{}", list_id);
+        self.put(&path, &ArchiveList { closed: false })
+    }
+
+    /// Archive every card in a list in one request.
+    pub fn archive_all_list_cards(&self, list_id: &str) -> Result<()> {
+        let path = format!("/lists/{}/archiveAllCards", list_id);
+        self.post_empty_void(&path)
     }
 
     pub fn move_list(&self, list_id: &str, position: &str) -> Result<List> {
@@ -433,7 +916,144 @@ impl TrelloClient {
     // Board operations
 
     pub fn get_member_boards(&self) -> Result<Vec<Board>> {
-        self.get("/members/me/boards?filter=open")
+        self.get_member_boards_filtered("open", None)
+    }
+
+    /// Fetch the current member's boards using a Trello board filter (`open`, `closed`, `all`,
+    /// `members`, `organization`, `public`, `starred`), optionally only those in the workspace
+    /// with ID `organization_id`.
+    pub fn get_member_boards_filtered(
+        &self,
+        filter: &str,
+        organization_id: Option<&str>,
+    ) -> Result<Vec<Board>> {
+        let boards: Vec<Board> = self.get_with_query(
+            "/members/me/boards",
+            &[
+                ("filter", filter),
+                (
+                    "fields",
+                    "id,name,shortUrl,closed,dateLastActivity,idOrganization",
+                ),
+            ],
+        )?;
+        // The member boards endpoint can't filter by workspace, so do it here
+        Ok(match organization_id {
+            Some(org) => boards
+                .into_iter()
+                .filter(|b| b.id_organization.as_deref() == Some(org))
+                .collect(),
+            None => boards,
+        })
+    }
+
+    pub fn get_organization_boards(&self, org_id: &str) -> Result<Vec<Board>> {
+        self.get_organization_boards_filtered(org_id, "open")
+    }
+
+    /// Fetch a workspace's boards using a Trello board filter (`open`, `closed`, `all`).
+    /// Includes boards the current member can see through the workspace without being
+    /// a board member.
+    pub fn get_organization_boards_filtered(
+        &self,
+        org_id: &str,
+        filter: &str,
+    ) -> Result<Vec<Board>> {
+        let path = format!("/organizations/{}/boards", org_id);
+        self.get_with_query(
+            &path,
+            &[
+                ("filter", filter),
+                (
+                    "fields",
+                    "id,name,shortUrl,closed,dateLastActivity,idOrganization",
+                ),
+            ],
+        )
+    }
+
+    pub fn get_member_organizations(&self) -> Result<Vec<Organization>> {
+        self.get_with_query(
+            "/members/me/organizations",
+            &[("fields", "id,name,displayName")],
+        )
+    }
+
+    /// Resolve a workspace ID, short name (exact, case-insensitive) or display name
+    /// substring to a workspace ID.
+    pub fn resolve_organization_id(&self, organization: &str) -> Result<String> {
+        if looks_like_id(organization) {
+            return Ok(organization.to_string());
+        }
+
+        let organizations = self
+            .get_member_organizations()
+            .context("Failed to fetch workspaces")?;
+        if let Some(org) = organizations
+            .iter()
+            .find(|o| o.name.eq_ignore_ascii_case(organization))
+        {
+            return Ok(org.id.clone());
+        }
+
+        let items: Vec<NamedItem> = organizations
+            .into_iter()
+            .map(|o| NamedItem {
+                id: o.id,
+                name: o.display_name,
+                context: o.name,
+            })
+            .collect();
+        find_unique_match(&items, organization)
+    }
+
+    /// Fetch a board's most recent actions, newest first, optionally only those whose
+    /// type is in the comma-separated `filter` (e.g. `createCard,commentCard`).
+    pub fn get_board_actions(
+        &self,
+        board_id: &str,
+        limit: usize,
+        filter: Option<&str>,
+    ) -> Result<Vec<Action>> {
+        let page_size = limit.min(MAX_ACTIONS_PER_PAGE);
+        let path = format!("/boards/{}/actions", board_id);
+        let page_size_param = page_size.to_string();
+        let mut params = vec![("limit", page_size_param.as_str())];
+        if let Some(filter) = filter {
+            params.push(("filter", filter));
+        }
+        let mut actions = self.get_paginated_up_to(
+            &path,
+            &params,
+            |action: &Action| Some(action.id.clone()),
+            page_size,
+            limit,
+        )?;
+        actions.truncate(limit);
+        Ok(actions)
+    }
+
+    /// Fetch every action on a board newer than the action `since_id`, newest first,
+    /// requesting `page_size` actions at a time.
+    pub fn get_board_actions_since(
+        &self,
+        board_id: &str,
+        since_id: &str,
+        page_size: usize,
+    ) -> Result<Vec<Action>> {
+        let path = format!("/boards/{}/actions", board_id);
+        self.get_paginated(
+            &path,
+            &[("since", since_id), ("limit", &page_size.to_string())],
+            |action: &Action| Some(action.id.clone()),
+            page_size,
+        )
+    }
+
+    /// Fetch every webhook registered with the client's API token.
+    pub fn get_token_webhooks(&self) -> Result<Vec<Webhook>> {
+        let path = format!("/tokens/{}/webhooks", self.api_token);
+        self.get(&path)
     }
 
     pub fn get_board(&self, board_id: &str) -> Result<Board> {
@@ -441,16 +1061,116 @@ impl TrelloClient {
         self.get(&path)
     }
 
+    pub fn close_board(&self, board_id: &str) -> Result<Board> {
+        let path = format!("/boards/{}/closed", board_id);
+        self.put(&path, &UpdateBoardClosed { value: true })
+    }
+
+    pub fn reopen_board(&self, board_id: &str) -> Result<Board> {
+        let path = format!("/boards/{}/closed", board_id);
+        self.put(&path, &UpdateBoardClosed { value: false })
+    }
+
+    /// Fetch a board with its open lists, open cards (IDs only) and members.
+    pub fn get_board_with_details(&self, board_id: &str) -> Result<BoardWithDetails> {
+        let path = format!("/boards/{}", board_id);
+        self.get_with_query(
+            &path,
+            &[
+                ("fields", "id,name,shortUrl,closed"),
+                ("lists", "open"),
+                ("list_fields", "id,name,idBoard,pos"),
+                ("cards", "open"),
+                ("card_fields", "id,idList"),
+                ("members", "all"),
+                ("member_fields", "username,fullName"),
+            ],
+        )
+    }
+
     pub fn get_board_cards(&self, board_id: &str) -> Result<Vec<Card>> {
         let path = format!("/boards/{}/cards", board_id);
+        self.get_with_query(&path, &[("filter", "open")])
+    }
+
+    /// Fetch cards on a board that changed after the given action ID.
+    pub fn get_board_cards_since(&self, board_id: &str, since_id: &str) -> Result<Vec<Card>> {
+        let path = format!("/boards/{}/cards", board_id);
+        self.get_with_query(&path, &[("since", since_id)])
+    }
+
+    /// ID of the most recent action on a board, if it has any.
+    pub fn get_board_latest_action_id(&self, board_id: &str) -> Result<Option<String>> {
+        let path = format!("/boards/{}/actions", board_id);
+        let actions: Vec<ActionRef> =
+            self.get_with_query(&path, &[("limit", "1"), ("fields", "id")])?;
+        Ok(actions.into_iter().next().map(|a| a.id))
+    }
+
+    /// Fetch cards on a board using a Trello card filter (`open`, `closed`, `all`).
+    pub fn get_board_cards_filtered(&self, board_id: &str, filter: &str) -> Result<Vec<Card>> {
+        let path = format!("/boards/{}/cards/{}", board_id, filter);
         self.get(&path)
     }
+
+    /// Resolve a board by ID, or by a case-insensitive name substring that matches exactly one board.
+    pub fn resolve_board(&self, board: &str) -> Result<Board> {
+        self.resolve_board_with_filter(board, "open")
+    }
+
+    /// Resolve a board ID or name substring among the current member's boards
+    /// matching a Trello board filter (`open`, `closed`, `all`).
+    pub fn resolve_board_with_filter(&self, board: &str, filter: &str) -> Result<Board> {
+        if looks_like_id(board) {
+            return self
+                .get_board(board)
+                .with_context(|| format!("Board ID '{}' not found or inaccessible", board));
+        }
+
+        let board_lower = board.to_lowercase();
+        let mut matches: Vec<Board> = self
+            .get_member_boards_filtered(filter, None)
+            .context("Failed to fetch boards")?
+            .into_iter()
+            .filter(|b| b.name.to_lowercase().contains(&board_lower))
+            .collect();
+
+        match matches.len() {
+            0 => Err(TrelloError::NotFound(format!("No boards matching '{}' found", board)).into()),
+            1 => Ok(matches.remove(0)),
+            _ => {
+                let names = matches
+                    .iter()
+                    .map(|b| b.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(
+                    TrelloError::User(format!("Multiple boards match '{}': {}", board, names))
+                        .into(),
+                )
+            }
+        }
+    }
+
+    pub fn get_board_members(&self, board_id: &str) -> Result<Vec<BoardMember>> {
+        let path = format!("/boards/{}/members", board_id);
+        self.get_with_query(&path, &[("fields", "username,fullName,memberType")])
+    }
+
+    pub fn invite_board_member(&self, board_id: &str, email: &str, role: &str) -> Result<()> {
+        let path = format!("/boards/{}/members", board_id);
+        let body = InviteMember {
+            email: email.to_string(),
+            member_type: role.to_string(),
+        };
+        self.put::<serde_json::Value, _>(&path, &body)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    use crate::config::CredentialSource;
     use std::collections::HashMap;
 
     fn test_client() -> TrelloClient {
@@ -458,29 +1178,108 @@ mod tests {
             client: Client::new(),
             api_key: "test_key".to_string(),
             api_token: "test_token".to_string(),
+            cache: None,
+            audit: RefCell::new(AuditLogger::disabled()),
         }
     }
 
-    struct MockSource(HashMap<String, String>);
-
-    impl MockSource {
-        fn new() -> Self {
-            MockSource(HashMap::new())
+    pub(crate) fn test_card(id: &str, name: &str, pos: f64) -> Card {
+        Card {
+            id: id.to_string(),
+            name: name.to_string(),
+            desc: String::new(),
+            id_board: "b".to_string(),
+            id_list: "l".to_string(),
+            id_labels: vec![],
+            closed: false,
+            pos,
+            cover: None,
+            start: None,
+            due: None,
+            url: String::new(),
+            date_last_activity: None,
+            short_url: None,
+            short_link: None,
+            badges: None,
+            id_members_voted: vec![],
+            id_members: vec![],
         }
+    }
 
-        fn with(vars: &[(&str, &str)]) -> Self {
-            let map = vars
-                .iter()
-                .map(|(k, v)| (k.to_string(), v.to_string()))
-                .collect();
-            MockSource(map)
+    /// Serve `pages` in order, recording the cursor each page was requested with.
+    fn fake_pages(
+        pages: Vec<Vec<u32>>,
+        cursors: &RefCell<Vec<Option<String>>>,
+    ) -> impl FnMut(Option<&str>) -> Result<Vec<u32>> + '_ {
+        let mut pages = pages.into_iter();
+        move |before| {
+            cursors.borrow_mut().push(before.map(str::to_string));
+            Ok(pages.next().expect("requested too many pages"))
         }
     }
 
-    impl CredentialSource for MockSource {
-        fn get(&self, key: &str) -> Option<String> {
-            self.0.get(key).cloned()
-        }
+    #[test]
+    fn collect_pages_stops_on_short_page() {
+        let cursors = RefCell::new(Vec::new());
+        let pages = vec![vec![1, 2], vec![3, 4], vec![5]];
+        let items = collect_pages(
+            fake_pages(pages, &cursors),
+            |n| Some(n.to_string()),
+            2,
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(items, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            *cursors.borrow(),
+            vec![None, Some("2".to_string()), Some("4".to_string())]
+        );
+    }
+
+    #[test]
+    fn collect_pages_stops_on_empty_page() {
+        let cursors = RefCell::new(Vec::new());
+        let pages = vec![vec![1, 2], vec![]];
+        let items = collect_pages(
+            fake_pages(pages, &cursors),
+            |n| Some(n.to_string()),
+            2,
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(cursors.borrow().len(), 2);
+
+        let cursors = RefCell::new(Vec::new());
+        let items = collect_pages(
+            fake_pages(vec![vec![]], &cursors),
+            |n| Some(n.to_string()),
+            2,
+            usize::MAX,
+        )
+        .unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn collect_pages_propagates_errors() {
+        let result = collect_pages(
+            |_| -> Result<Vec<u32>> { Err(anyhow::anyhow!("boom")) },
+            |n| Some(n.to_string()),
+            2,
+            usize::MAX,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn collect_pages_stops_at_max_items() {
+        let cursors = RefCell::new(Vec::new());
+        let pages = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        let items =
+            collect_pages(fake_pages(pages, &cursors), |n| Some(n.to_string()), 2, 3).unwrap();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+        assert_eq!(cursors.borrow().len(), 2);
     }
 
     #[test]
@@ -493,23 +1292,97 @@ mod tests {
     #[test]
     fn add_auth_adds_query_params() {
         let client = test_client();
-        let url = client.add_auth("https://api.trello.com/1/cards/123");
+        let request = client.build_get_request("/cards/123", &[]).unwrap();
         assert_eq!(
-            url,
+            request.url().as_str(),
             "https://api.trello.com/1/cards/123?key=test_key&token=test_token"
         );
     }
 
+    #[test]
+    fn get_request_keeps_query_in_path() {
+        let client = test_client();
+        let request = client
+            .build_get_request("/cards/123/actions?filter=commentCard&before=abc", &[])
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.trello.com/1/cards/123/actions?filter=commentCard&before=abc&key=test_key&token=test_token"
+        );
+    }
+
+    #[test]
+    fn patch_request_uses_patch_method_and_json_body() {
+        let client = test_client();
+        let request = client
+            .build_patch_request(
+                "/cards/abc",
+                &UpdateCardName {
+                    name: "Renamed".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::PATCH);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.trello.com/1/cards/abc?key=test_key&token=test_token"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::CONTENT_TYPE],
+            "application/json"
+        );
+        let body = request.body().and_then(|b| b.as_bytes()).unwrap();
+        assert_eq!(body, br#"{"name":"Renamed"}"#);
+    }
+
+    #[test]
+    fn post_empty_request_has_no_body() {
+        let client = test_client();
+        let request = client
+            .build_post_empty_request("/lists/abc/archiveAllCards")
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(
+            request.url().as_str(),
+            "https://api.trello.com/1/lists/abc/archiveAllCards?key=test_key&token=test_token"
+        );
+        assert_eq!(request.headers()[reqwest::header::CONTENT_LENGTH], "0");
+        assert!(
+            request
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .is_none()
+        );
+        assert!(request.body().is_none());
+    }
+
     #[test]
     fn add_auth_appends_to_existing_query() {
         let client = test_client();
-        let url = client.add_auth("https://api.trello.com/1/cards/123?fields=name");
+        let request = client
+            .build_get_request("/cards/123", &[("fields", "name")])
+            .unwrap();
         assert_eq!(
-            url,
+            request.url().as_str(),
             "https://api.trello.com/1/cards/123?fields=name&key=test_key&token=test_token"
         );
     }
 
+    #[test]
+    fn get_request_encodes_query_params() {
+        let client = test_client();
+        let request = client
+            .build_get_request("/search", &[("query", "To Do & Done"), ("idBoards", "a=b")])
+            .unwrap();
+        assert_eq!(
+            request.url().query(),
+            Some("query=To+Do+%26+Done&idBoards=a%3Db&key=test_key&token=test_token")
+        );
+
+        let pairs: Vec<(String, String)> = request.url().query_pairs().into_owned().collect();
+        assert_eq!(pairs[0], ("query".to_string(), "To Do & Done".to_string()));
+    }
+
     #[test]
     fn client_new_from_config() {
         use std::fs;
@@ -522,8 +1395,8 @@ mod tests {
         writeln!(file, "api_key = \"config_key\"").unwrap();
         writeln!(file, "api_token = \"config_token\"").unwrap();
 
-        // Use empty mock source to force file-based loading
-        let source = MockSource::new();
+        // Use empty source to force file-based loading
+        let source: HashMap<String, String> = HashMap::new();
         let config = Config::load_from_source(&source, config_path).unwrap();
         let client = TrelloClient::new(&config);
 
@@ -535,10 +1408,12 @@ mod tests {
     fn client_new_from_env_source() {
         use tempfile::TempDir;
 
-        let source = MockSource::with(&[
-            ("TRELLO_API_KEY", "env_key"),
-            ("TRELLO_API_TOKEN", "env_token"),
-        ]);
+        let source: HashMap<String, String> = [
+            ("TRELLO_API_KEY".to_string(), "env_key".to_string()),
+            ("TRELLO_API_TOKEN".to_string(), "env_token".to_string()),
+        ]
+        .into_iter()
+        .collect();
 
         // Config path doesn't need to exist when env vars are set
         let temp_dir = TempDir::new().unwrap();
@@ -550,6 +1425,55 @@ mod tests {
         assert_eq!(client.api_token, "env_token");
     }
 
+    #[test]
+    fn with_timeout_accepts_limited_and_unlimited_timeouts() {
+        let config = Config::new("key", "token");
+        assert!(
+            TrelloClient::new(&config)
+                .with_timeout(Some(Duration::from_secs(5)))
+                .is_ok()
+        );
+        assert!(TrelloClient::new(&config).with_timeout(None).is_ok());
+    }
+
+    #[test]
+    fn with_cache_enables_cache_only_when_requested() {
+        let source: HashMap<String, String> = [
+            ("TRELLO_API_KEY".to_string(), "key".to_string()),
+            ("TRELLO_API_TOKEN".to_string(), "token".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config =
+            Config::load_from_source(&source, temp_dir.path().join("config.toml")).unwrap();
+
+        assert!(TrelloClient::with_cache(&config, true).cache.is_some());
+        assert!(TrelloClient::with_cache(&config, false).cache.is_none());
+        assert!(TrelloClient::new(&config).cache.is_none());
+    }
+
+    #[test]
+    fn request_cache_stores_and_clears_entries() {
+        let mut cache = RequestCache::default();
+        let url = "https://api.trello.com/1/boards/123/lists";
+        assert_eq!(cache.get(url), None);
+
+        cache.insert(url.to_string(), "[]".to_string());
+        assert_eq!(cache.get(url), Some("[]"));
+
+        cache.clear();
+        assert_eq!(cache.get(url), None);
+    }
+
+    #[test]
+    fn cache_key_excludes_credentials() {
+        let client = test_client();
+        let key = client.build_url("/members/me/boards");
+        assert!(!key.contains("test_key"));
+        assert!(!key.contains("test_token"));
+    }
+
     #[test]
     fn find_unique_match_returns_one_match() {
         let items = vec![
@@ -605,73 +1529,88 @@ mod tests {
     }
 
     #[test]
-    fn compute_position_returns_top_for_first_or_less() {
-        let cards = vec![
-            Card {
+    fn resolve_card_returns_id_without_lookup() {
+        // A 24-hex ID is used as-is, so no request is made
+        let client = test_client();
+        let id = client
+            .resolve_card("507f1f77bcf86cd799439011", Some("Work"))
+            .unwrap();
+        assert_eq!(id, "507f1f77bcf86cd799439011");
+    }
+
+    #[test]
+    fn find_unique_match_disambiguates_cards_across_boards() {
+        let items = vec![
+            NamedItem {
                 id: "1".to_string(),
-                name: "A".to_string(),
-                desc: String::new(),
-                id_board: "b".to_string(),
-                id_list: "l".to_string(),
-                id_labels: vec![],
-                closed: false,
-                pos: 10.0,
+                name: "Fix login bug".to_string(),
+                context: "Work".to_string(),
             },
-            Card {
+            NamedItem {
                 id: "2".to_string(),
-                name: "B".to_string(),
-                desc: String::new(),
-                id_board: "b".to_string(),
-                id_list: "l".to_string(),
-                id_labels: vec![],
-                closed: false,
-                pos: 20.0,
+                name: "Fix logout bug".to_string(),
+                context: "Personal".to_string(),
             },
         ];
 
+        assert_eq!(find_unique_match(&items, "LOGOUT").unwrap(), "2");
+
+        let err = find_unique_match(&items, "fix").unwrap_err().to_string();
+        assert!(err.contains("Multiple matches found for 'fix'"));
+        assert!(err.contains("Fix login bug (board: Work)"));
+        assert!(err.contains("Fix logout bug (board: Personal)"));
+        assert!(err.contains("-b/--board"));
+    }
+
+    #[test]
+    fn find_unique_match_resolves_lists_across_boards() {
+        let list = |id: &str, name: &str, board: &str| NamedItem {
+            id: id.to_string(),
+            name: name.to_string(),
+            context: board.to_string(),
+        };
+        let items = vec![
+            list("1", "To Do", "Work"),
+            list("2", "Done", "Work"),
+            list("3", "Backlog", "Home"),
+            list("4", "Done", "Home"),
+        ];
+
+        assert_eq!(find_unique_match(&items, "to do").unwrap(), "1");
+
+        let err = find_unique_match(&items, "Done").unwrap_err();
+        assert_eq!(
+            crate::error::exit_code(&err),
+            crate::error::ExitCode::UserError
+        );
+        let message = err.to_string();
+        assert!(message.contains("Done (board: Work)"));
+        assert!(message.contains("Done (board: Home)"));
+
+        let err = find_unique_match(&items, "Icebox").unwrap_err();
+        assert_eq!(
+            crate::error::exit_code(&err),
+            crate::error::ExitCode::NotFound
+        );
+    }
+
+    #[test]
+    fn compute_position_returns_top_for_first_or_less() {
+        let cards = vec![test_card("1", "A", 10.0), test_card("2", "B", 20.0)];
+
         assert_eq!(compute_position(&cards, 1), "top");
     }
 
     #[test]
     fn compute_position_returns_bottom_beyond_length() {
-        let cards = vec![Card {
-            id: "1".to_string(),
-            name: "A".to_string(),
-            desc: String::new(),
-            id_board: "b".to_string(),
-            id_list: "l".to_string(),
-            id_labels: vec![],
-            closed: false,
-            pos: 10.0,
-        }];
+        let cards = vec![test_card("1", "A", 10.0)];
 
         assert_eq!(compute_position(&cards, 2), "bottom");
     }
 
     #[test]
     fn compute_position_returns_midpoint_for_middle() {
-        let cards = vec![
-            Card {
-                id: "1".to_string(),
-                name: "A".to_string(),
-                desc: String::new(),
-                id_board: "b".to_string(),
-                id_list: "l".to_string(),
-                id_labels: vec![],
-                closed: false,
-                pos: 10.0,
-            },
-            Card {
-                id: "2".to_string(),
-                name: "B".to_string(),
-                desc: String::new(),
-                id_board: "b".to_string(),
-                id_list: "l".to_string(),
-                id_labels: vec![],
-                closed: false,
-                pos: 20.0,
-            },
-        ];
+        let cards = vec![test_card("1", "A", 10.0), test_card("2", "B", 20.0)];
 
         assert_eq!(compute_position(&cards, 2), "15");
     }
@@ -681,4 +1620,26 @@ mod tests {
         let cards: Vec<Card> = vec![];
         assert_eq!(compute_position(&cards, 5), "top");
     }
+
+    #[test]
+    fn position_next_to_uses_midpoint_between_neighbours() {
+        let positions = [10.0, 20.0, 40.0];
+
+        assert_eq!(position_next_to(&positions, 1, true), "30");
+        assert_eq!(position_next_to(&positions, 1, false), "15");
+        assert_eq!(position_next_to(&positions, 0, true), "15");
+        assert_eq!(position_next_to(&positions, 2, false), "30");
+    }
+
+    #[test]
+    fn position_next_to_handles_list_ends() {
+        let positions = [10.0, 20.0];
+
+        assert_eq!(position_next_to(&positions, 0, false), "top");
+        assert_eq!(position_next_to(&positions, 1, true), "bottom");
+
+        let single = [10.0];
+        assert_eq!(position_next_to(&single, 0, false), "top");
+        assert_eq!(position_next_to(&single, 0, true), "bottom");
+    }
 }
@@ -1,30 +1,57 @@
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use toml_edit::DocumentMut;
+
+use crate::error::TrelloError;
 
 /// Trait for abstracting environment variable access, enabling testability.
-pub(crate) trait CredentialSource {
+pub trait CredentialSource {
     fn get(&self, key: &str) -> Option<String>;
 }
 
 /// Default implementation that reads from actual environment variables.
-struct EnvSource;
+pub struct VarSource;
 
-impl CredentialSource for EnvSource {
+impl CredentialSource for VarSource {
     fn get(&self, key: &str) -> Option<String> {
         env::var(key).ok()
     }
 }
 
+/// In-memory implementation, useful for constructing configs without touching real env vars.
+impl CredentialSource for HashMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).cloned()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     api_key: String,
     api_token: String,
 }
 
+const CONFIG_TEMPLATE: &str = "\
+# trello-cli configuration
+# Get an API key from https://trello.com/power-ups/admin and generate a token for it.
+# TRELLO_API_KEY and TRELLO_API_TOKEN take precedence over these values when set.
+
+# api_key = \"\"
+# api_token = \"\"
+";
+
 impl Config {
+    pub fn new(api_key: &str, api_token: &str) -> Self {
+        Config {
+            api_key: api_key.to_string(),
+            api_token: api_token.to_string(),
+        }
+    }
+
     pub fn api_key(&self) -> &str {
         &self.api_key
     }
@@ -33,19 +60,20 @@ impl Config {
         &self.api_token
     }
 
+    /// Load credentials from the environment or the default config file.
     pub fn load() -> Result<Self> {
         let config_path = Self::config_path()?;
         Self::load_from_path(config_path)
     }
 
+    /// Load credentials from the environment or the config file at `config_path`.
     pub fn load_from_path(config_path: PathBuf) -> Result<Self> {
-        Self::load_from_source(&EnvSource, config_path)
+        Self::load_from_source(&VarSource, config_path)
     }
 
-    pub(crate) fn load_from_source(
-        source: &impl CredentialSource,
-        config_path: PathBuf,
-    ) -> Result<Self> {
+    /// Load credentials from `source` when it has both `TRELLO_API_KEY` and
+    /// `TRELLO_API_TOKEN`, and from the config file at `config_path` otherwise.
+    pub fn load_from_source(source: &dyn CredentialSource, config_path: PathBuf) -> Result<Self> {
         // Try environment variables first (both must be set)
         let key_env = source.get("TRELLO_API_KEY");
         let token_env = source.get("TRELLO_API_TOKEN");
@@ -74,43 +102,96 @@ impl Config {
 
             // Validate both fields are present (non-empty)
             if config.api_key.is_empty() {
-                bail!(
+                return Err(TrelloError::Auth(format!(
                     "Config file {} is missing api_key field",
                     config_path.display()
-                );
+                ))
+                .into());
             }
             if config.api_token.is_empty() {
-                bail!(
+                return Err(TrelloError::Auth(format!(
                     "Config file {} is missing api_token field",
                     config_path.display()
-                );
+                ))
+                .into());
             }
 
             return Ok(config);
         }
 
-        bail!(
+        Err(TrelloError::Auth(format!(
             "Failed to load Trello credentials.\nChecked:\n  \
              - Environment variables TRELLO_API_KEY and TRELLO_API_TOKEN: {}\n  \
              - Config file {}: not found",
             env_status,
             config_path.display()
-        );
+        ))
+        .into())
     }
 
+    /// Save credentials, preserving any other settings already in the config file.
     pub fn save(api_key: &str, api_token: &str) -> Result<()> {
         let path = Self::config_path()?;
+        Self::update_fields_at_path(&path, &[("api_key", api_key), ("api_token", api_token)])
+    }
+
+    /// Set a single top-level key in the config file, preserving other keys and formatting.
+    #[allow(dead_code)] // Will be used as config settings are added
+    pub fn update_field(key: &str, value: &str) -> Result<()> {
+        let path = Self::config_path()?;
+        Self::update_fields_at_path(&path, &[(key, value)])
+    }
+
+    fn update_fields_at_path(path: &Path, fields: &[(&str, &str)]) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).with_context(|| {
                 format!("Failed to create config directory {}", parent.display())
             })?;
         }
-        let contents = format!("api_key = \"{}\"\napi_token = \"{}\"\n", api_key, api_token);
-        fs::write(&path, contents)
+
+        // Start from the existing document; an unreadable or malformed file is replaced
+        let mut doc = fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| contents.parse::<DocumentMut>().ok())
+            .unwrap_or_default();
+        for (key, value) in fields {
+            doc[*key] = toml_edit::value(*value);
+        }
+
+        fs::write(path, doc.to_string())
             .with_context(|| format!("Failed to write config file {}", path.display()))?;
         Ok(())
     }
 
+    /// Check that the config file is readable, valid TOML. Returns whether it exists;
+    /// a missing file is not an error, since credentials may come from the environment.
+    pub fn check_file(path: &Path) -> Result<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str::<toml::Table>(&contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Create the config file from a commented template if it does not exist yet.
+    /// Returns whether a file was created.
+    pub fn ensure_exists(path: &Path) -> Result<bool> {
+        if path.exists() {
+            return Ok(false);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory {}", parent.display())
+            })?;
+        }
+        fs::write(path, CONFIG_TEMPLATE)
+            .with_context(|| format!("Failed to write config file {}", path.display()))?;
+        Ok(true)
+    }
+
     pub fn config_path() -> Result<PathBuf> {
         let config_dir =
             dirs::config_dir().context("Could not determine config directory for this platform")?;
@@ -121,10 +202,23 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use std::io::Write;
     use tempfile::TempDir;
 
+    #[test]
+    fn check_file_reports_missing_valid_and_malformed_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        assert!(!Config::check_file(&path).unwrap());
+
+        fs::write(&path, CONFIG_TEMPLATE).unwrap();
+        assert!(Config::check_file(&path).unwrap());
+
+        fs::write(&path, "api_key = \"unterminated").unwrap();
+        let err = Config::check_file(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("Failed to parse config file"));
+    }
+
     /// Mock credential source for testing without environment variable manipulation.
     struct MockSource(HashMap<String, String>);
 
@@ -302,6 +396,107 @@ mod tests {
         assert!(err.contains("missing"), "Error was: {}", err);
     }
 
+    #[test]
+    fn hash_map_source_provides_credentials() {
+        let mut source = HashMap::new();
+        source.insert("TRELLO_API_KEY".to_string(), "map_key".to_string());
+        source.insert("TRELLO_API_TOKEN".to_string(), "map_token".to_string());
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        let config = Config::load_from_source(&source, config_path).unwrap();
+        assert_eq!(config.api_key(), "map_key");
+        assert_eq!(config.api_token(), "map_token");
+    }
+
+    #[test]
+    fn partial_env_vars_without_file_error() {
+        let source = MockSource::with(&[("TRELLO_API_TOKEN", "only_token")]);
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        let err = Config::load_from_source(&source, config_path)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("only one set (both required)"),
+            "Error was: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn update_fields_preserves_unknown_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "# my settings\napi_key = \"old_key\"\napi_token = \"old_token\"\ndefault_board = \"Work\"\n",
+        )
+        .unwrap();
+
+        Config::update_fields_at_path(
+            &config_path,
+            &[("api_key", "new_key"), ("api_token", "new_token")],
+        )
+        .unwrap();
+
+        let contents = fs::read_to_string(&config_path).unwrap();
+        assert!(contents.contains("# my settings"), "Contents: {}", contents);
+        assert!(
+            contents.contains("default_board = \"Work\""),
+            "Contents: {}",
+            contents
+        );
+
+        let config = Config::load_from_source(&MockSource::new(), config_path).unwrap();
+        assert_eq!(config.api_key(), "new_key");
+        assert_eq!(config.api_token(), "new_token");
+    }
+
+    #[test]
+    fn update_fields_creates_missing_file_and_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("trello-cli").join("config.toml");
+
+        Config::update_fields_at_path(&config_path, &[("default_board", "Work")]).unwrap();
+
+        let contents = fs::read_to_string(&config_path).unwrap();
+        assert_eq!(contents.trim(), "default_board = \"Work\"");
+    }
+
+    #[test]
+    fn ensure_exists_writes_template_only_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("trello-cli").join("config.toml");
+
+        assert!(Config::ensure_exists(&config_path).unwrap());
+        let contents = fs::read_to_string(&config_path).unwrap();
+        assert!(contents.contains("# api_key"));
+        assert!(contents.parse::<DocumentMut>().is_ok());
+
+        fs::write(&config_path, "api_key = \"key\"\n").unwrap();
+        assert!(!Config::ensure_exists(&config_path).unwrap());
+        assert_eq!(
+            fs::read_to_string(&config_path).unwrap(),
+            "api_key = \"key\"\n"
+        );
+    }
+
+    #[test]
+    fn update_fields_replaces_malformed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(&config_path, "this is not valid toml {{{").unwrap();
+
+        Config::update_fields_at_path(&config_path, &[("api_key", "key"), ("api_token", "token")])
+            .unwrap();
+
+        let config = Config::load_from_source(&MockSource::new(), config_path).unwrap();
+        assert_eq!(config.api_key(), "key");
+        assert_eq!(config.api_token(), "token");
+    }
+
     #[test]
     fn toml_format_parsing() {
         let toml_content = r#"
@@ -0,0 +1,201 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+
+/// Process exit codes, so scripts can tell failure kinds apart without parsing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    /// A failure that doesn't fit any of the categories below
+    Failure = 1,
+    /// No matching cards, or the named card, list, board or label doesn't exist
+    NotFound = 2,
+    /// Credentials are missing or were rejected (HTTP 401)
+    AuthError = 3,
+    /// The API could not be reached or the request timed out
+    NetworkError = 4,
+    /// Invalid command-line input
+    UserError = 5,
+    /// The API returned any other unsuccessful response
+    ApiError = 6,
+}
+
+/// Errors that carry a category for the exit code. They are raised like any other
+/// error and may be wrapped in `anyhow` context; `exit_code` finds them in the chain.
+#[derive(Debug)]
+pub enum TrelloError {
+    Auth(String),
+    NotFound(String),
+    User(String),
+    /// HTTP 429, with the number of seconds from the `Retry-After` header if it was sent
+    RateLimited {
+        retry_after: Option<u64>,
+        body: String,
+    },
+    Api {
+        status: StatusCode,
+        body: String,
+    },
+}
+
+impl TrelloError {
+    /// Categorise an unsuccessful API response by its status code.
+    pub fn from_response(status: StatusCode, body: String) -> Self {
+        let message = || format!("API request failed with status {}: {}", status, body);
+        match status {
+            StatusCode::UNAUTHORIZED => TrelloError::Auth(message()),
+            StatusCode::NOT_FOUND => TrelloError::NotFound(message()),
+            StatusCode::TOO_MANY_REQUESTS => TrelloError::RateLimited {
+                retry_after: None,
+                body,
+            },
+            _ => TrelloError::Api { status, body },
+        }
+    }
+
+    /// Categorise an unsuccessful API response, consuming it to read the body.
+    pub fn from_http_response(response: reqwest::blocking::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().unwrap_or_default();
+        match Self::from_response(status, body) {
+            TrelloError::RateLimited { body, .. } => TrelloError::RateLimited { retry_after, body },
+            err => err,
+        }
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            TrelloError::Auth(_) => ExitCode::AuthError,
+            TrelloError::NotFound(_) => ExitCode::NotFound,
+            TrelloError::User(_) => ExitCode::UserError,
+            TrelloError::RateLimited { .. } | TrelloError::Api { .. } => ExitCode::ApiError,
+        }
+    }
+}
+
+impl fmt::Display for TrelloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrelloError::Auth(message)
+            | TrelloError::NotFound(message)
+            | TrelloError::User(message) => f.write_str(message),
+            TrelloError::RateLimited { retry_after, body } => {
+                write!(f, "API rate limit exceeded")?;
+                match retry_after {
+                    Some(secs) => write!(f, "; retry after {} seconds", secs)?,
+                    None => write!(f, "; try again later")?,
+                }
+                if !body.is_empty() {
+                    write!(f, ": {}", body)?;
+                }
+                Ok(())
+            }
+            TrelloError::Api { status, body } => {
+                write!(f, "API request failed with status {}: {}", status, body)
+            }
+        }
+    }
+}
+
+/// Seconds to wait from a `Retry-After` header. Only the delay-seconds form is
+/// understood; an HTTP date gives `None`.
+fn parse_retry_after(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+impl std::error::Error for TrelloError {}
+
+/// Exit code for an error: the category of the first `TrelloError` in its chain,
+/// `NetworkError` for transport failures, and `Failure` for anything else.
+pub fn exit_code(err: &anyhow::Error) -> ExitCode {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<TrelloError>() {
+            return e.exit_code();
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>()
+            && (e.is_connect() || e.is_timeout() || e.is_request())
+        {
+            return ExitCode::NetworkError;
+        }
+    }
+    ExitCode::Failure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn from_response_categorises_by_status() {
+        let code = |status| TrelloError::from_response(status, String::new()).exit_code();
+        assert_eq!(code(StatusCode::UNAUTHORIZED), ExitCode::AuthError);
+        assert_eq!(code(StatusCode::NOT_FOUND), ExitCode::NotFound);
+        assert_eq!(code(StatusCode::BAD_REQUEST), ExitCode::ApiError);
+        assert_eq!(code(StatusCode::TOO_MANY_REQUESTS), ExitCode::ApiError);
+        assert_eq!(code(StatusCode::INTERNAL_SERVER_ERROR), ExitCode::ApiError);
+    }
+
+    #[test]
+    fn api_errors_keep_the_original_message() {
+        let err = TrelloError::from_response(StatusCode::UNAUTHORIZED, "invalid token".into());
+        assert_eq!(
+            err.to_string(),
+            "API request failed with status 401 Unauthorized: invalid token"
+        );
+        let err = TrelloError::from_response(StatusCode::BAD_REQUEST, "bad".into());
+        assert_eq!(
+            err.to_string(),
+            "API request failed with status 400 Bad Request: bad"
+        );
+    }
+
+    #[test]
+    fn rate_limit_errors_say_when_to_retry() {
+        let err = TrelloError::from_response(StatusCode::TOO_MANY_REQUESTS, String::new());
+        assert!(matches!(
+            err,
+            TrelloError::RateLimited {
+                retry_after: None,
+                ..
+            }
+        ));
+        assert_eq!(err.to_string(), "API rate limit exceeded; try again later");
+
+        let err = TrelloError::RateLimited {
+            retry_after: Some(10),
+            body: "API_TOKEN_LIMIT_EXCEEDED".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "API rate limit exceeded; retry after 10 seconds: API_TOKEN_LIMIT_EXCEEDED"
+        );
+
+        assert_eq!(parse_retry_after("120"), Some(120));
+        assert_eq!(parse_retry_after(" 5 "), Some(5));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn exit_code_looks_through_context() {
+        let err: anyhow::Result<()> = Err(TrelloError::NotFound("No card".into()).into());
+        let err = err
+            .context("Failed to resolve card 'x'")
+            .context("outer")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), ExitCode::NotFound);
+
+        let err = reqwest::blocking::get("http://127.0.0.1:1/")
+            .context("Failed to send GET request")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), ExitCode::NetworkError);
+
+        let err = anyhow::anyhow!("something else");
+        assert_eq!(exit_code(&err), ExitCode::Failure);
+    }
+}
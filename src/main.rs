@@ -1,28 +1,50 @@
+mod audit;
 mod client;
 mod config;
+mod error;
 mod models;
+mod oauth;
+mod template;
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::io::{self, Write};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
-use regex::RegexBuilder;
-use serde::Serialize;
+use clap::{Parser, Subcommand, ValueEnum};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
 
-use client::{TrelloClient, compute_position};
-use config::Config;
+use audit::AuditLogger;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use client::{TrelloClient, compute_position, position_next_to};
+use config::{Config, CredentialSource, VarSource};
+use error::{ExitCode, TrelloError};
 use models::CreateCard;
+use models::{
+    Action, Board, BoardMember, BoardWithDetails, Card, CardBadges, Checklist, Label, List, Member,
+    Webhook,
+};
+use template::TemplateEngine;
 
 #[derive(Parser)]
 #[command(name = "trello")]
 #[command(version, about = "A CLI for managing Trello cards and lists")]
 struct Cli {
+    /// HTTP request timeout in seconds for this invocation (0 disables the timeout)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u64).range(0..=300))]
+    timeout: Option<u64>,
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // Parsed once at startup, so the size doesn't matter
 enum Commands {
     /// Save API credentials to the config file
     Login {
@@ -32,6 +54,26 @@ enum Commands {
         /// Trello API token
         #[arg(long)]
         api_token: Option<String>,
+        /// Verify the credentials against the API before saving them
+        #[arg(long)]
+        verify: bool,
+        /// Obtain a token by authorizing in the browser (OAuth 1.0a) instead of pasting one
+        #[arg(long, conflicts_with = "api_token")]
+        oauth: bool,
+        /// Trello API secret, used to sign OAuth requests
+        #[arg(long, requires = "oauth")]
+        api_secret: Option<String>,
+    },
+    /// Show the currently authenticated user
+    Whoami {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Locate or edit the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
     /// Manage cards
     Card {
@@ -43,6 +85,17 @@ enum Commands {
         #[command(subcommand)]
         command: ListCommands,
     },
+    /// Poll a board and print new activity as it happens (Ctrl-C to stop)
+    Watch {
+        /// The board ID or board name substring
+        board: String,
+        /// Seconds to wait between polls
+        #[arg(long = "interval", value_name = "SECS", default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        interval_secs: u64,
+        /// Print each action as a line of JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Manage boards
     Board {
         #[command(subcommand)]
@@ -56,23 +109,81 @@ enum CardCommands {
     Create {
         /// The list ID or list name substring
         list: String,
-        /// The card name
-        name: String,
-        /// Set the card description
+        /// The card name (optional with --template-file)
+        #[arg(required_unless_present = "template_file")]
+        name: Option<String>,
+        /// Set the card description ("-" reads stdin, "@path" reads a file)
         #[arg(short, long)]
         description: Option<String>,
-        /// Position: "top", "bottom", or numeric ordinal
-        #[arg(short, long, default_value = "bottom")]
-        position: String,
+        /// Copy the description of this card (ID or name substring); --description overrides it
+        #[arg(long, value_name = "CARD")]
+        copy_description_from: Option<String>,
+        /// Position: "top", "bottom", or numeric ordinal [default: bottom]
+        #[arg(short, long)]
+        position: Option<String>,
+        /// Place the card directly after this card in the list (ID or name substring)
+        #[arg(long, value_name = "CARD", conflicts_with_all = ["position", "before_card"])]
+        after_card: Option<String>,
+        /// Place the card directly before this card in the list (ID or name substring)
+        #[arg(long, value_name = "CARD", conflicts_with = "position")]
+        before_card: Option<String>,
         /// Filter by board name or ID when resolving list names
         #[arg(short, long)]
         board: Option<String>,
+        /// Apply a label to the new card (repeatable)
+        #[arg(short, long)]
+        label: Vec<String>,
+        /// Assign a board member to the new card by username or full name (repeatable)
+        #[arg(short, long)]
+        member: Vec<String>,
+        /// Add an empty checklist with this name to the new card (repeatable)
+        #[arg(long, value_name = "NAME")]
+        checklist: Vec<String>,
+        /// Add a checklist with items, as "<name>:<item1>,<item2>" (repeatable)
+        #[arg(long, value_name = "NAME:ITEMS", value_parser = parse_checklist_spec)]
+        checklist_items: Vec<ChecklistSpec>,
+        /// Set the card's due date (YYYY-MM-DD, local time)
+        #[arg(long, value_parser = parse_date)]
+        due: Option<NaiveDate>,
+        /// Set the card's start date (YYYY-MM-DD, local time)
+        #[arg(long, value_parser = parse_date)]
+        start: Option<NaiveDate>,
+        /// Fail if the list already has a card with this name (case-insensitive)
+        #[arg(long, conflicts_with = "rename_duplicate")]
+        error_on_duplicate: bool,
+        /// If the list already has a card with this name, append " <SUFFIX>" to the new card's name
+        #[arg(long, value_name = "SUFFIX")]
+        rename_duplicate: Option<String>,
+        /// Read name, description, position, labels and due from a TOML or JSON file
+        #[arg(long, value_name = "PATH")]
+        template_file: Option<PathBuf>,
+        /// Replace "{KEY}" in the template with VALUE (repeatable)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_var, requires = "template_file")]
+        var: Vec<(String, String)>,
+        /// Print only the new card's ID, without a trailing newline
+        #[arg(short, long)]
+        quiet: bool,
     },
-    /// Update a card (description, labels, comment, archive)
+    /// Update a card (name, description, labels, comment, archive)
     Update {
-        /// The card ID
+        /// The card ID or card name substring
         card_id: String,
-        /// Update the card's description
+        /// Filter by board name or ID when resolving card names
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Rename the card
+        #[arg(long, conflicts_with_all = ["name_from_file", "name_from_stdin"])]
+        name: Option<String>,
+        /// Rename the card to the first line of a UTF-8 text file
+        #[arg(long, value_name = "PATH", conflicts_with = "name_from_stdin")]
+        name_from_file: Option<PathBuf>,
+        /// Rename the card to the first line read from stdin
+        #[arg(long)]
+        name_from_stdin: bool,
+        /// Warn when the new name is longer than N characters
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_NAME_LENGTH)]
+        max_name_length: usize,
+        /// Update the card's description ("-" reads stdin, "@path" reads a file)
         #[arg(short, long)]
         description: Option<String>,
         /// Apply a label to the card (repeatable)
@@ -81,15 +192,45 @@ enum CardCommands {
         /// Remove a label from the card (repeatable)
         #[arg(long)]
         clear_label: Vec<String>,
-        /// Add a comment to the card
+        /// Remove every label from the card
+        #[arg(long, conflicts_with_all = ["label", "clear_label"])]
+        clear_all_labels: bool,
+        /// Add a comment to the card (repeatable, posted in order)
         #[arg(short, long)]
-        comment: Option<String>,
+        comment: Vec<String>,
+        /// Add a comment read from a UTF-8 text file
+        #[arg(long, value_name = "PATH", conflicts_with = "comment")]
+        comment_from_file: Option<PathBuf>,
         /// Archive the card
         #[arg(short, long)]
         archive: bool,
         /// Restore (unarchive) the card
         #[arg(short, long)]
         restore: bool,
+        /// Set the card cover color
+        #[arg(long, value_parser = TRELLO_COLORS)]
+        cover_color: Option<String>,
+        /// Remove the card cover
+        #[arg(long)]
+        clear_cover: bool,
+        /// Change the card's position: "top", "bottom", or numeric ordinal
+        #[arg(short, long)]
+        position: Option<String>,
+        /// Set the card's start date (YYYY-MM-DD, local time)
+        #[arg(long, value_parser = parse_date)]
+        start: Option<NaiveDate>,
+        /// Remove the card's start date
+        #[arg(long)]
+        clear_start: bool,
+        /// Assign yourself to the card
+        #[arg(long, conflicts_with = "unassign_me")]
+        assign_me: bool,
+        /// Remove yourself from the card's members
+        #[arg(long)]
+        unassign_me: bool,
+        /// Print nothing on success; errors and warnings still go to stderr
+        #[arg(short, long)]
+        quiet: bool,
     },
     /// Change a card's position
     Move {
@@ -106,36 +247,396 @@ enum CardCommands {
     /// Find cards matching a pattern
     Find {
         /// Regex pattern to match card names
+        #[arg(required_unless_present = "json_schema", default_value = "")]
         pattern: String,
-        /// Filter by board name or ID
+        /// Filter by board name or ID. Repeat to search several boards
         #[arg(short, long)]
-        board: Option<String>,
+        board: Vec<String>,
         /// Filter by list name or ID
         #[arg(short, long)]
         list: Option<String>,
         /// Output as JSON
+        ///
+        /// Always an array of card results, even for a single match:
+        /// `[{"id", "board", "list", "title", "list_closed", "url"}]`, plus `description` with
+        /// --show-description. With --limit, the array is the `items` field of
+        /// `{"total", "offset", "limit", "items"}`.
         #[arg(long)]
         json: bool,
+        /// Maximum number of results to show (0 means unlimited)
+        #[arg(long, default_value_t = 0)]
+        limit: usize,
+        /// Number of results to skip before showing any
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Suppress the pagination footer
+        #[arg(short, long)]
+        quiet: bool,
+        /// Exit 0 without a message when no cards match (default: exit 2)
+        #[arg(long)]
+        quiet_on_empty: bool,
+        /// Pick a card from the results interactively and print its ID
+        #[arg(short, long, conflicts_with = "json")]
+        interactive: bool,
+        /// Match card names exactly (case-insensitive) instead of as a regex
+        #[arg(long, conflicts_with = "case_sensitive")]
+        exact: bool,
+        /// Match the regex case-sensitively
+        #[arg(long)]
+        case_sensitive: bool,
+        /// Let "^" and "$" match at line breaks within a name, not just at its ends
+        #[arg(long, conflicts_with = "exact")]
+        multiline: bool,
+        /// Only match the pattern as whole words (wraps it in "\b...\b")
+        #[arg(long, conflicts_with = "exact")]
+        whole_word: bool,
+        /// Search only the board with this ID, skipping board name resolution
+        #[arg(long, conflicts_with = "board")]
+        board_id: Option<String>,
+        /// Only match cards with this label. Repeat to require all of them:
+        /// `--label A --label B` matches cards that have both A and B
+        #[arg(long)]
+        label: Vec<String>,
+        /// Only match cards with this label. Repeat to match cards that have any of them
+        #[arg(long)]
+        has_label: Vec<String>,
+        /// Only match cards with no labels applied
+        #[arg(long, conflicts_with_all = ["label", "has_label"])]
+        no_labels: bool,
+        /// Skip boards with no activity in the last N days
+        #[arg(long, value_name = "N")]
+        active_within_days: Option<u32>,
+        /// Add a URL column with each card's short URL (always included in JSON)
+        #[arg(long)]
+        show_url: bool,
+        /// Add a column with the start of each card's description (the full text in JSON)
+        #[arg(long, conflicts_with_all = ["interactive", "group_by_list"])]
+        show_description: bool,
+        /// Take at most N matching cards from each board
+        #[arg(long, value_name = "N")]
+        limit_per_board: Option<usize>,
+        /// Print results as a board/list hierarchy instead of a table
+        #[arg(long, conflicts_with_all = ["json", "interactive", "show_url"])]
+        group_by_list: bool,
+        /// Only match cards modified after this action ID
+        #[arg(long, value_name = "ACTION_ID")]
+        since_id: Option<String>,
+        /// Print the latest action ID to stderr, for use with --since-id next time
+        #[arg(long)]
+        print_cursor: bool,
+        /// Only match cards with a checklist item matching this regex (one extra request per card)
+        #[arg(long, value_name = "PATTERN")]
+        with_checklist_item: Option<String>,
+        /// Check the checklists of at most N cards when using --with-checklist-item
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 100,
+            requires = "with_checklist_item"
+        )]
+        max_cards_to_check: usize,
+        /// Write results to this file instead of stdout (truncating it unless --append is set)
+        #[arg(long, value_name = "PATH", conflicts_with = "interactive")]
+        output_file: Option<PathBuf>,
+        /// Format the results with a template file using "{% for card in cards %}...{% endfor %}"
+        /// loops and "{{ card.<field> }}". Fields: id, board, list, title, url, labels, list_closed
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["json", "interactive", "group_by_list", "ndjson", "ndjson_with_meta", "show_url"]
+        )]
+        output_template_file: Option<PathBuf>,
+        /// Append to --output-file instead of truncating it
+        #[arg(long, requires = "output_file")]
+        append: bool,
+        /// Only match cards that have a description
+        #[arg(long, conflicts_with = "no_description")]
+        has_description: bool,
+        /// Only match cards with an empty description
+        #[arg(long)]
+        no_description: bool,
+        /// Output one JSON object per line (NDJSON)
+        #[arg(long, alias = "json-lines", conflicts_with_all = ["json", "interactive", "group_by_list"])]
+        ndjson: bool,
+        /// Like --ndjson, but start with a metadata line and tag every line with its type
+        #[arg(long, conflicts_with_all = ["json", "interactive", "group_by_list", "ndjson"])]
+        ndjson_with_meta: bool,
+        /// Also search cards in archived lists
+        #[arg(long)]
+        include_closed_lists: bool,
+        /// Only match cards assigned to you
+        #[arg(long, conflicts_with = "not_assigned")]
+        assigned_to_me: bool,
+        /// Only match cards with no members assigned
+        #[arg(long)]
+        not_assigned: bool,
+        /// Only match cards with at least one checklist item
+        #[arg(long, conflicts_with = "no_checklist")]
+        has_checklist: bool,
+        /// Only match cards with no checklist items
+        #[arg(long, conflicts_with = "checklist_complete")]
+        no_checklist: bool,
+        /// Only match cards whose checklist items are all complete
+        #[arg(long)]
+        checklist_complete: bool,
+        /// Which of your boards to search, using Trello's board filter
+        #[arg(
+            long,
+            value_name = "TYPE",
+            default_value = "open",
+            value_parser = ["open", "closed", "all", "members", "organization", "public", "starred"]
+        )]
+        board_filter: String,
+        /// Search all your boards, including closed ones (same as --board-filter all)
+        #[arg(long, conflicts_with = "board_filter")]
+        all_boards: bool,
+        /// Only search boards in this workspace (ID, short name or display name)
+        #[arg(long, value_name = "ORG", conflicts_with = "board_id")]
+        organization: Option<String>,
+        /// Fail instead of searching more than N boards
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        max_boards: usize,
+        /// Search every matching board, even if there are more than --max-boards
+        #[arg(long)]
+        confirm_large: bool,
+        /// Skip cards in lists whose name contains this (repeatable; any match excludes)
+        #[arg(long, value_name = "LIST")]
+        not_in_list: Vec<String>,
+        /// Also search boards you can see through workspace membership without being a member
+        #[arg(long, conflicts_with_all = ["board_filter", "board_id"])]
+        include_org_boards: bool,
+        /// Print only the number of matching cards (`{"count": N}` with --json)
+        #[arg(
+            long,
+            alias = "output-count",
+            conflicts_with_all = [
+                "interactive", "group_by_list", "ndjson", "ndjson_with_meta", "output_file",
+                "output_template_file", "show_url", "show_description", "limit", "offset"
+            ]
+        )]
+        count: bool,
+        /// Print the JSON Schema of the --json output and exit, without contacting Trello
+        #[arg(long)]
+        json_schema: bool,
+    },
+    /// Archive all cards matching a pattern
+    BulkArchive {
+        /// Regex pattern to match card names
+        pattern: String,
+        /// Filter by board name or ID
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Filter by list name or ID
+        #[arg(short, long)]
+        list: Option<String>,
+        /// Show what would be archived without archiving anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Archive without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Move all cards matching a pattern to another list
+    BulkMove {
+        /// Regex pattern to match card names (empty matches all cards)
+        pattern: String,
+        /// Destination list name or ID
+        #[arg(long)]
+        to_list: String,
+        /// Filter by board name or ID when resolving the destination list
+        #[arg(long)]
+        to_board: Option<String>,
+        /// Filter source cards by board name or ID
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Filter source cards by list name or ID
+        #[arg(short, long)]
+        list: Option<String>,
+        /// Position in the destination list
+        #[arg(short, long, default_value = "bottom", value_parser = ["top", "bottom"])]
+        position: String,
+        /// Show what would be moved without moving anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Move without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+        /// Don't print a line for each moved card
+        #[arg(short, long)]
+        quiet: bool,
     },
-    /// Show detailed information about a card
+    /// Show detailed information about one or more cards
     Show {
-        /// The card ID
-        card_id: String,
+        /// Card IDs or card name substrings
+        #[arg(required_unless_present = "json_schema", value_name = "CARD")]
+        card_ids: Vec<String>,
+        /// Filter by board name or ID when resolving card names
+        #[arg(short, long)]
+        board: Option<String>,
         /// Output as JSON
+        ///
+        /// An object with the card's details: `{"id", "name", "board", "list", "labels",
+        /// "description", "start", "archived", "cover_color", "vote_count", "has_voted", "due",
+        /// "last_activity", "url"}`, plus `position`, `comments`, `siblings` and `webhooks`
+        /// when requested.
+        /// With several cards, an array of these objects.
         #[arg(long)]
         json: bool,
+        /// With --json, print the card in the same schema as one `card find --json` result:
+        /// `{"id", "board", "list", "title", "list_closed", "url"}`
+        #[arg(
+            long,
+            requires = "json",
+            conflicts_with_all = ["comments", "show_position", "board_context", "webhooks"]
+        )]
+        compact: bool,
         /// Include comments
         #[arg(long)]
         comments: bool,
+        /// Only show comments made on or after this date (YYYY-MM-DD)
+        #[arg(long, requires = "comments", value_parser = parse_date)]
+        since: Option<NaiveDate>,
+        /// Only show the N most recent comments
+        #[arg(long = "last-N-comments", value_name = "N", requires = "comments")]
+        last_n_comments: Option<usize>,
+        /// Highlight comments matching this regex (case-insensitive) and dim the rest
+        #[arg(long, value_name = "PATTERN", requires = "comments")]
+        highlight_comments: Option<String>,
+        /// Render the description's Markdown for the terminal
+        #[arg(long, conflicts_with = "raw_description")]
+        render_markdown: bool,
+        /// Print the description verbatim, without indentation
+        #[arg(long)]
+        raw_description: bool,
+        /// Format output with a template, e.g. "{name} [{list}]". Placeholders: {id}, {name},
+        /// {board}, {list}, {description}, {labels}, {archived}, {due}, {url}
+        #[arg(long, conflicts_with = "json")]
+        output_format: Option<String>,
+        /// Show the card's ordinal position within its list
+        #[arg(long)]
+        show_position: bool,
+        /// Also list the other cards in the same list, marking this one
+        #[arg(long)]
+        board_context: bool,
+        /// Show the webhooks registered with your API token that watch this card
+        #[arg(long)]
+        webhooks: bool,
+        /// After the card details, show the card's short URL as a QR code
+        #[arg(long, conflicts_with_all = ["json", "output_format", "field"])]
+        qr: bool,
+        /// Print the JSON Schema of the --json output and exit, without contacting Trello
+        #[arg(long)]
+        json_schema: bool,
+        /// Print only this field (repeatable). Fields: id, name, board, list, labels,
+        /// description, archived, due, url
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["output_format", "compact"])]
+        field: Vec<String>,
+    },
+    /// Vote on a card as the current member
+    Vote {
+        /// The card ID or card name substring
+        card_id: String,
+        /// Remove your vote instead of adding one
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Reply to a comment by posting a new comment that mentions its author
+    CommentReply {
+        /// The card ID or card name substring
+        card_id: String,
+        /// The ID of the comment action to reply to
+        action_id: String,
+        /// The reply text
+        text: String,
     },
 }
 
+/// Card fields that `list cards` can sort by.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum SortField {
+    Pos,
+    Name,
+    Due,
+    #[value(name = "date_last_activity")]
+    DateLastActivity,
+}
+
+/// Board fields that `board list` can sort by.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum BoardSortField {
+    Name,
+    Activity,
+    CardCount,
+}
+
+/// Color names accepted by Trello for labels and card covers.
+const TRELLO_COLORS: [&str; 10] = [
+    "green", "yellow", "orange", "red", "purple", "blue", "sky", "lime", "pink", "black",
+];
+
+#[derive(Serialize)]
+struct WhoamiResult {
+    id: String,
+    username: String,
+    full_name: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BoardResult {
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_last_activity: Option<String>,
+    short_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    card_count: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct BoardDetail {
+    id: String,
+    name: String,
+    short_url: Option<String>,
+    closed: bool,
+    lists: Vec<ListSummary>,
+    member_count: usize,
+}
+
+#[derive(Serialize)]
+struct ListSummary {
+    id: String,
+    name: String,
+    card_count: usize,
+}
+
+#[derive(Serialize)]
+struct ListResult {
+    id: String,
+    board: String,
+    name: String,
+}
+
 #[derive(Serialize)]
 struct CardResult {
     id: String,
     board: String,
     list: String,
     title: String,
+    #[serde(skip)]
+    board_id: String,
+    #[serde(skip)]
+    list_id: String,
+    #[serde(skip)]
+    closed: bool,
+    list_closed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pos: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<Vec<String>>,
+    url: Option<String>,
+    /// The card's full description, only output with `card find --show-description`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -146,9 +647,107 @@ struct ShowCardResult {
     list: String,
     labels: Vec<LabelInfo>,
     description: String,
+    start: Option<String>,
     archived: bool,
+    cover_color: Option<String>,
+    vote_count: Option<u32>,
+    has_voted: Option<bool>,
+    due: Option<String>,
+    last_activity: Option<String>,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<PositionInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     comments: Option<Vec<CommentInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    siblings: Option<Vec<SiblingInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    webhooks: Option<Vec<Webhook>>,
+    #[serde(skip)]
+    short_url: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SiblingInfo {
+    ordinal: usize,
+    id: String,
+    name: String,
+    current: bool,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct PositionInfo {
+    ordinal: usize,
+    total: usize,
+    raw: f64,
+}
+
+/// Cards shown on each side of the current card by `card show --show-position`.
+const POSITION_CONTEXT: usize = 2;
+
+/// 1-based position of a card among the cards of its list, ordered by `pos`.
+/// Returns `None` when the card is not in `cards` (e.g. it is archived).
+fn card_ordinal(cards: &[Card], card_id: &str) -> Option<PositionInfo> {
+    let mut sorted: Vec<&Card> = cards.iter().collect();
+    sorted.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+    sorted
+        .iter()
+        .position(|c| c.id == card_id)
+        .map(|index| PositionInfo {
+            ordinal: index + 1,
+            total: sorted.len(),
+            raw: sorted[index].pos,
+        })
+}
+
+/// The cards around `current_id` in position order, numbered and with the current
+/// card marked, keeping `context` cards on each side. Skipped cards are shown as `...`.
+/// Returns an empty string when the card is not in `cards`.
+fn render_position_context(cards: &[Card], current_id: &str, context: usize) -> String {
+    let mut sorted: Vec<&Card> = cards.iter().collect();
+    sorted.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+    let Some(index) = sorted.iter().position(|c| c.id == current_id) else {
+        return String::new();
+    };
+
+    let first = index.saturating_sub(context);
+    let last = (index + context).min(sorted.len() - 1);
+    let mut out = String::new();
+    if first > 0 {
+        out.push_str("  ...\n");
+    }
+    for (i, card) in sorted.iter().enumerate().take(last + 1).skip(first) {
+        let name = sanitize_field(&card.name);
+        if i == index {
+            out.push_str(&format!(
+                "  {}. [this card] {}  ← you are here\n",
+                i + 1,
+                name
+            ));
+        } else {
+            out.push_str(&format!("  {}. {}\n", i + 1, name));
+        }
+    }
+    if last + 1 < sorted.len() {
+        out.push_str("  ...\n");
+    }
+    out
+}
+
+/// The cards of a list in position order, flagging the one with `card_id`.
+fn list_siblings(cards: &[Card], card_id: &str) -> Vec<SiblingInfo> {
+    let mut sorted: Vec<&Card> = cards.iter().collect();
+    sorted.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| SiblingInfo {
+            ordinal: i + 1,
+            id: c.id.clone(),
+            name: c.name.clone(),
+            current: c.id == card_id,
+        })
+        .collect()
 }
 
 #[derive(Serialize)]
@@ -159,9 +758,14 @@ struct LabelInfo {
 
 #[derive(Serialize)]
 struct CommentInfo {
+    /// The comment's action ID, for `card comment-reply`
+    id: String,
     date: String,
     author: String,
     text: String,
+    /// Whether the comment matches `--highlight-comments`, only set with that flag
+    #[serde(skip_serializing_if = "Option::is_none")]
+    highlight_match: Option<bool>,
 }
 
 fn looks_like_id(input: &str) -> bool {
@@ -190,1159 +794,8989 @@ fn format_comment_date(iso_date: &str) -> String {
     }
 }
 
-#[derive(Subcommand)]
-enum ListCommands {
-    /// Show detailed information about a list
-    Show {
-        /// The list ID
-        list_id: String,
-        /// Output as JSON
-        #[arg(long)]
-        json: bool,
-    },
-    /// Change a list's position
-    Move {
-        /// The list ID
-        list_id: String,
-        /// Position: "top", "bottom", or a numeric value
-        position: String,
-    },
+/// A checklist to create on a new card, with its items in order.
+#[derive(Debug, Clone, PartialEq)]
+struct ChecklistSpec {
+    name: String,
+    items: Vec<String>,
 }
 
-#[derive(Subcommand)]
-enum BoardCommands {
-    /// Show detailed information about a board
-    Show {
-        /// The board ID
-        board_id: String,
-        /// Output as JSON
-        #[arg(long)]
-        json: bool,
-    },
+/// Parse `<name>:<item1>,<item2>` into a checklist spec.
+fn parse_checklist_spec(s: &str) -> Result<ChecklistSpec, String> {
+    let (name, items) = s
+        .split_once(':')
+        .ok_or_else(|| format!("invalid checklist '{}', expected <name>:<item1>,<item2>", s))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("checklist '{}' has an empty name", s));
+    }
+    let items: Vec<String> = items.split(',').map(|i| i.trim().to_string()).collect();
+    if items.iter().any(String::is_empty) {
+        return Err(format!("checklist '{}' has an empty item", s));
+    }
+    Ok(ChecklistSpec {
+        name: name.to_string(),
+        items,
+    })
 }
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("Error: {e:#}");
-        std::process::exit(1);
+/// Combine `--checklist` names and `--checklist-items` specs, in the order given,
+/// merging items into a single checklist when a name appears more than once.
+fn merge_checklists(names: Vec<String>, specs: Vec<ChecklistSpec>) -> Vec<ChecklistSpec> {
+    let mut merged: Vec<ChecklistSpec> = Vec::new();
+    let all = names
+        .into_iter()
+        .map(|name| ChecklistSpec {
+            name,
+            items: Vec::new(),
+        })
+        .chain(specs);
+    for spec in all {
+        match merged.iter_mut().find(|c| c.name == spec.name) {
+            Some(existing) => existing.items.extend(spec.items),
+            None => merged.push(spec),
+        }
     }
+    merged
 }
 
-fn prompt_value(prompt: &str) -> Result<String> {
-    print!("{}", prompt);
-    io::stdout().flush()?;
-    let mut value = String::new();
-    io::stdin()
-        .read_line(&mut value)
-        .context("Failed to read input")?;
-    Ok(value.trim().to_string())
+fn parse_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid variable '{}', expected KEY=VALUE", s)),
+    }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
+/// Card fields read from a `card create --template-file`.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct CardTemplate {
+    name: Option<String>,
+    description: Option<String>,
+    position: Option<String>,
+    labels: Vec<String>,
+    due: Option<String>,
+}
 
-    if let Commands::Login { api_key, api_token } = cli.command {
-        let key = match api_key {
-            Some(k) => k,
-            None => prompt_value("API key: ")?,
-        };
-        let token = match api_token {
-            Some(t) => t,
-            None => {
-                rpassword::prompt_password("API token: ").context("Failed to read API token")?
-            }
-        };
-        Config::save(&key, &token)?;
-        let path = Config::config_path()?;
-        println!("Credentials saved to {}", path.display());
-        return Ok(());
+/// Matches a `{KEY}` placeholder in a card template, capturing `KEY`.
+const TEMPLATE_PLACEHOLDER: &str = r"\{([A-Za-z_][A-Za-z0-9_]*)\}";
+
+/// Replace each `{KEY}` in `template` with its value from `vars`, in a single pass,
+/// so substituted values are never expanded again. Unknown keys are left as is.
+fn apply_variables(template: &str, vars: &HashMap<String, String>) -> String {
+    let placeholder = Regex::new(TEMPLATE_PLACEHOLDER).expect("valid regex");
+    placeholder
+        .replace_all(template, |caps: &regex::Captures| {
+            vars.get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Parse a card template (JSON if the path ends in `.json`, TOML otherwise) and
+/// substitute `vars` into its fields. Fails if any `{VAR}` placeholder is left over.
+fn parse_card_template(
+    text: &str,
+    path: &Path,
+    vars: &HashMap<String, String>,
+) -> Result<CardTemplate> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let template: CardTemplate = if is_json {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(text).map_err(|e| e.to_string())
     }
+    .map_err(|e| {
+        TrelloError::User(format!(
+            "Invalid card template '{}': {}",
+            path.display(),
+            e.trim()
+        ))
+    })?;
 
-    let config = Config::load()?;
-    let client = TrelloClient::new(&config);
+    let placeholder = Regex::new(TEMPLATE_PLACEHOLDER).expect("valid regex");
+    let mut unresolved: Vec<&str> = [
+        &template.name,
+        &template.description,
+        &template.position,
+        &template.due,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(&template.labels)
+    .flat_map(|s| placeholder.captures_iter(s))
+    .filter(|caps| !vars.contains_key(&caps[1]))
+    .map(|caps| caps.get(0).expect("whole match").as_str())
+    .collect();
+    unresolved.sort_unstable();
+    unresolved.dedup();
+    if !unresolved.is_empty() {
+        return Err(TrelloError::User(format!(
+            "Unresolved template variables in '{}': {}. Pass them with --var KEY=VALUE.",
+            path.display(),
+            unresolved.join(", ")
+        ))
+        .into());
+    }
 
-    match cli.command {
-        Commands::Login { .. } => unreachable!(),
-        Commands::Card { command } => match command {
-            CardCommands::Create {
-                list,
-                name,
-                description,
-                position,
-                board,
-            } => {
-                let list_id = client
-                    .resolve_list(&list, board.as_deref())
-                    .with_context(|| format!("Failed to resolve list '{}'", list))?;
+    let apply = |field: Option<String>| field.map(|s| apply_variables(&s, vars));
+    Ok(CardTemplate {
+        name: apply(template.name),
+        description: apply(template.description),
+        position: apply(template.position),
+        labels: template
+            .labels
+            .iter()
+            .map(|l| apply_variables(l, vars))
+            .collect(),
+        due: apply(template.due),
+    })
+}
 
-                let pos = match position.as_str() {
-                    "top" | "bottom" => position.clone(),
-                    _ => {
-                        if let Ok(target_pos) = position.parse::<usize>() {
-                            let mut cards = client.get_list_cards(&list_id).with_context(|| {
-                                format!("Failed to fetch cards for list '{}'", list_id)
-                            })?;
-                            cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
-                            compute_position(&cards, target_pos)
-                        } else {
-                            position.clone()
-                        }
-                    }
-                };
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD", s))
+}
 
-                let body = CreateCard {
-                    name,
-                    pos,
-                    id_list: list_id,
-                    desc: description,
-                };
+/// Midnight local time on `date`, as a UTC ISO 8601 timestamp for the Trello API.
+fn local_date_to_utc(date: NaiveDate) -> String {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+    let utc = match Local.from_local_datetime(&midnight).earliest() {
+        Some(local) => local.with_timezone(&Utc),
+        // Midnight skipped by a DST transition; fall back to UTC midnight
+        None => midnight.and_utc(),
+    };
+    utc.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
 
-                let card = client.create_card(&body).context("Failed to create card")?;
-                let list = client
-                    .get_list(&card.id_list)
-                    .with_context(|| format!("Failed to fetch list '{}'", card.id_list))?;
+/// Narrow chronologically ordered comments to those on or after `since`, then
+/// to the last `last_n` of what remains.
+fn filter_comments(
+    mut actions: Vec<Action>,
+    since: Option<NaiveDate>,
+    last_n: Option<usize>,
+) -> Vec<Action> {
+    if let Some(since) = since {
+        let since = since.format("%Y-%m-%d").to_string();
+        // ISO 8601 timestamps sort lexicographically, so the date prefix compares directly
+        actions.retain(|a| a.date.get(..10).is_some_and(|d| d >= since.as_str()));
+    }
+    if let Some(n) = last_n {
+        let skip = actions.len().saturating_sub(n);
+        actions.drain(..skip);
+    }
+    actions
+}
 
-                println!(
-                    "Created card '{}' ({}) in list '{}'",
-                    card.name, card.id, list.name
-                );
-            }
-            CardCommands::Update {
-                card_id,
-                description,
-                label,
-                clear_label,
-                comment,
-                archive,
-                restore,
-            } => {
-                if description.is_none()
-                    && label.is_empty()
-                    && clear_label.is_empty()
-                    && comment.is_none()
-                    && !archive
-                    && !restore
-                {
-                    eprintln!("Error: at least one update flag must be provided");
-                    std::process::exit(1);
-                }
+fn build_name_regex(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| TrelloError::User(format_regex_error(pattern, &e)).into())
+}
 
-                if archive && restore {
-                    eprintln!("Error: --archive and --restore are mutually exclusive");
-                    std::process::exit(1);
-                }
+/// Describe a regex compile error with a caret under the offending character:
+///
+/// ```text
+/// Invalid regex pattern: 'foo[bar'
+///        foo[bar
+///           ^  unclosed character class
+/// ```
+fn format_regex_error(pattern: &str, err: &regex::Error) -> String {
+    const INDENT: &str = "       ";
+    let header = format!("Invalid regex pattern: '{}'", pattern);
+    // regex::Error only carries a preformatted message, so parse the pattern again
+    // to get the error's position
+    let (span, kind) = match regex_syntax::Parser::new().parse(pattern) {
+        Err(regex_syntax::Error::Parse(e)) => (*e.span(), e.kind().to_string()),
+        Err(regex_syntax::Error::Translate(e)) => (*e.span(), e.kind().to_string()),
+        _ => return format!("{}: {}", header, err),
+    };
+    let line = pattern.lines().nth(span.start.line - 1).unwrap_or(pattern);
+    format!(
+        "{}\n{}{}\n{}{}^  {}",
+        header,
+        INDENT,
+        line,
+        INDENT,
+        " ".repeat(span.start.column - 1),
+        kind
+    )
+}
 
-                let needs_card = !label.is_empty()
-                    || !clear_label.is_empty()
-                    || comment.is_some()
-                    || archive
-                    || restore;
+/// `RegexBuilder` settings for a `card find` pattern.
+#[derive(Clone, Copy, Default)]
+struct RegexOptions {
+    case_sensitive: bool,
+    multiline: bool,
+    whole_word: bool,
+}
 
-                // Update description
-                let desc_card_name = if let Some(ref desc) = description {
-                    let card = client
-                        .update_card_description(&card_id, desc)
-                        .with_context(|| {
-                            format!("Failed to update description of card '{}'", card_id)
-                        })?;
-                    Some(card.name)
-                } else {
-                    None
-                };
+/// How card names are matched against a `card find` pattern.
+enum NameMatcher {
+    Regex(Regex),
+    /// Case-insensitive string equality, for names containing regex metacharacters
+    Exact(String),
+}
 
-                // Fetch card once for label/comment/archive operations
-                let card = if needs_card {
-                    Some(
-                        client
-                            .get_card(&card_id)
-                            .with_context(|| format!("Failed to fetch card '{}'", card_id))?,
-                    )
-                } else {
-                    None
-                };
-                let card_name = card
-                    .as_ref()
-                    .map(|c| c.name.clone())
-                    .or(desc_card_name)
-                    .unwrap_or_else(|| card_id.clone());
+impl NameMatcher {
+    fn new(pattern: &str, exact: bool, options: RegexOptions) -> Result<Self> {
+        if exact {
+            return Ok(NameMatcher::Exact(pattern.to_string()));
+        }
+        let full_pattern = if options.whole_word {
+            // Grouped so that alternations like "bug|fix" are bounded as a whole
+            format!(r"\b(?:{})\b", pattern)
+        } else {
+            pattern.to_string()
+        };
+        // Errors point into the pattern as the user typed it, without the word boundaries
+        let regex = RegexBuilder::new(&full_pattern)
+            .case_insensitive(!options.case_sensitive)
+            .multi_line(options.multiline)
+            .build()
+            .map_err(|e| TrelloError::User(format_regex_error(pattern, &e)))?;
+        Ok(NameMatcher::Regex(regex))
+    }
 
-                if description.is_some() {
-                    println!("Updated description of card '{}'", card_name);
-                }
+    fn is_match(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Regex(regex) => regex.is_match(name),
+            NameMatcher::Exact(pattern) => name.eq_ignore_ascii_case(pattern),
+        }
+    }
+}
 
-                // Apply/remove labels
-                if !label.is_empty() || !clear_label.is_empty() {
-                    let card = card.as_ref().unwrap();
-                    let board_labels =
-                        client.get_board_labels(&card.id_board).with_context(|| {
-                            format!("Failed to fetch labels for card '{}'", card_id)
-                        })?;
+/// Split boards into those active within `days` of `now` and the number skipped.
+/// Boards with a missing or unparseable activity date are kept.
+fn filter_active_boards(boards: Vec<Board>, days: u32, now: DateTime<Utc>) -> (Vec<Board>, usize) {
+    let cutoff = now - chrono::Duration::days(i64::from(days));
+    let total = boards.len();
+    let active: Vec<Board> = boards
+        .into_iter()
+        .filter(|b| {
+            b.date_last_activity
+                .as_deref()
+                .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                .is_none_or(|d| d >= cutoff)
+        })
+        .collect();
+    let skipped = total - active.len();
+    (active, skipped)
+}
 
-                    for label_name in &label {
-                        client
-                            .apply_label_by_name(card, &board_labels, label_name)
-                            .with_context(|| {
-                                format!(
-                                    "Failed to apply label '{}' to card '{}'",
-                                    label_name, card.name
-                                )
-                            })?;
-                        println!("Applied label '{}' to card '{}'", label_name, card.name);
-                    }
+/// Sort cards by `field`, reversed when `desc`. Cards missing an optional field
+/// sort last (or first with `nulls_first`) regardless of direction.
+fn sort_cards(cards: &mut [Card], field: SortField, desc: bool, nulls_first: bool) {
+    let directed = |o: Ordering| if desc { o.reverse() } else { o };
+    let optional = |a: Option<&String>, b: Option<&String>| match (a, b) {
+        (Some(a), Some(b)) => directed(a.cmp(b)),
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) if nulls_first => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) if nulls_first => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+    };
 
-                    for label_name in &clear_label {
-                        client
-                            .remove_label_by_name(card, &board_labels, label_name)
-                            .with_context(|| {
-                                format!(
-                                    "Failed to remove label '{}' from card '{}'",
-                                    label_name, card.name
-                                )
-                            })?;
-                        println!("Removed label '{}' from card '{}'", label_name, card.name);
-                    }
-                }
+    cards.sort_by(|a, b| match field {
+        SortField::Pos => directed(a.pos.total_cmp(&b.pos)),
+        SortField::Name => directed(a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+        SortField::Due => optional(a.due.as_ref(), b.due.as_ref()),
+        SortField::DateLastActivity => {
+            optional(a.date_last_activity.as_ref(), b.date_last_activity.as_ref())
+        }
+    });
+}
 
-                // Add comment
-                if let Some(ref text) = comment {
-                    let trimmed = text.trim();
-                    if !trimmed.is_empty() {
-                        client
-                            .add_comment_to_card(&card_id, trimmed)
-                            .with_context(|| {
-                                format!("Failed to add comment to card '{}'", card_id)
-                            })?;
-                        println!("Added comment to card '{}'", card_name);
-                    }
-                }
+/// Sort board results by `field`. Boards with no activity date or card count sort
+/// last in either direction.
+fn sort_boards(boards: &mut [BoardResult], field: BoardSortField, desc: bool) {
+    fn optional<T: Ord>(a: Option<T>, b: Option<T>, desc: bool) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) if desc => b.cmp(&a),
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
 
-                // Archive or restore
-                if archive {
-                    let card = card.as_ref().unwrap();
-                    client
-                        .archive_card(card)
-                        .with_context(|| format!("Failed to archive card '{}'", card_id))?;
-                    println!("Archived card '{}'", card_name);
-                } else if restore {
-                    let card = card.as_ref().unwrap();
-                    client
-                        .restore_card(card)
-                        .with_context(|| format!("Failed to restore card '{}'", card_id))?;
-                    println!("Restored card '{}'", card_name);
-                }
-            }
-            CardCommands::Move { card_id, position } => {
-                let card = client.move_card(&card_id, &position)?;
-                println!("Moved card '{}' to position {}", card.name, position);
-            }
-            CardCommands::Delete { card_id } => {
-                let card = client
-                    .get_card(&card_id)
-                    .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
-                client
-                    .delete_card(&card_id)
-                    .with_context(|| format!("Failed to delete card '{}'", card_id))?;
-                println!("Deleted card '{}' ({})", card.name, card.id);
-            }
-            CardCommands::Find {
-                pattern,
-                board,
-                list,
-                json,
-            } => {
-                let regex = RegexBuilder::new(&pattern)
-                    .case_insensitive(true)
-                    .build()
-                    .context("Invalid regex pattern")?;
-
-                // Fetch boards
-                let boards = if let Some(ref board_filter) = board {
-                    if looks_like_id(board_filter) {
-                        let b = client.get_board(board_filter).with_context(|| {
-                            format!("Board ID '{}' not found or inaccessible", board_filter)
-                        })?;
-                        vec![b]
-                    } else {
-                        let all_boards = client
-                            .get_member_boards()
-                            .context("Failed to fetch boards")?;
-                        let board_filter_lower = board_filter.to_lowercase();
-                        let filtered: Vec<_> = all_boards
-                            .into_iter()
-                            .filter(|b| b.name.to_lowercase().contains(&board_filter_lower))
-                            .collect();
-                        if filtered.is_empty() {
-                            eprintln!("No boards matching '{}' found", board_filter);
-                            return Ok(());
-                        }
-                        filtered
-                    }
-                } else {
-                    let all_boards = client
-                        .get_member_boards()
-                        .context("Failed to fetch boards")?;
-                    if all_boards.is_empty() {
-                        eprintln!("No boards found");
-                        return Ok(());
-                    }
-                    all_boards
-                };
+    boards.sort_by(|a, b| match field {
+        BoardSortField::Name => {
+            let order = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+            if desc { order.reverse() } else { order }
+        }
+        BoardSortField::Activity => optional(
+            a.date_last_activity.as_ref(),
+            b.date_last_activity.as_ref(),
+            desc,
+        ),
+        BoardSortField::CardCount => optional(a.card_count, b.card_count, desc),
+    });
+}
 
-                let mut results: Vec<CardResult> = Vec::new();
-                let list_filter_lower = list.as_ref().map(|s| s.to_lowercase());
+/// The `n` boards with the latest activity, most recent first. Boards with no
+/// activity date sort last.
+fn most_recent_boards(mut boards: Vec<Board>, n: usize) -> Vec<Board> {
+    boards.sort_by(
+        |a, b| match (&a.date_last_activity, &b.date_last_activity) {
+            (Some(a), Some(b)) => b.cmp(a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+    );
+    boards.truncate(n);
+    boards
+}
 
-                for b in &boards {
-                    let cards = client
-                        .get_board_cards(&b.id)
-                        .with_context(|| format!("Failed to fetch cards for board '{}'", b.name))?;
-                    let lists = client
-                        .get_board_lists(&b.id)
-                        .with_context(|| format!("Failed to fetch lists for board '{}'", b.name))?;
-                    let list_map: HashMap<String, String> =
-                        lists.into_iter().map(|l| (l.id, l.name)).collect();
+/// Criteria beyond the name pattern that a card must meet to match.
+#[derive(Debug, Default)]
+struct CardFilter {
+    labels: LabelFilter,
+    /// `Some(true)` requires a non-empty description, `Some(false)` an empty one
+    has_description: Option<bool>,
+    /// Also match cards in archived lists (by default only open lists are searched)
+    include_closed_lists: bool,
+    assignee: Option<AssigneeFilter>,
+    checklist: Option<ChecklistFilter>,
+}
 
-                    for card in cards {
-                        if !regex.is_match(&card.name) {
-                            continue;
-                        }
-                        let Some(list_name) = list_map.get(&card.id_list) else {
-                            continue;
-                        };
+/// What a card's checklists must look like, judged from its badge counts.
+#[derive(Debug, Clone, Copy)]
+enum ChecklistFilter {
+    /// At least one checklist item
+    Present,
+    /// No checklist items
+    Absent,
+    /// At least one checklist item, all of them checked
+    Complete,
+}
 
-                        // Apply list filter if specified
-                        if list_filter_lower.as_ref().is_some_and(|filter_lower| {
-                            !list_name.to_lowercase().contains(filter_lower)
-                        }) {
-                            continue;
-                        }
+/// Who a card must be assigned to.
+#[derive(Debug)]
+enum AssigneeFilter {
+    /// The member with this ID must be among the card's members
+    Member(String),
+    /// The card must have no members
+    Unassigned,
+}
 
-                        results.push(CardResult {
-                            id: card.id,
-                            board: b.name.clone(),
-                            list: list_name.clone(),
-                            title: card.name,
-                        });
-                    }
-                }
+impl CardFilter {
+    fn matches_members(&self, id_members: &[String]) -> bool {
+        match &self.assignee {
+            None => true,
+            Some(AssigneeFilter::Member(id)) => id_members.contains(id),
+            Some(AssigneeFilter::Unassigned) => id_members.is_empty(),
+        }
+    }
 
-                if results.is_empty() {
-                    eprintln!("No cards found");
-                    return Ok(());
-                }
+    fn matches_checklists(&self, badges: Option<&CardBadges>) -> bool {
+        let (total, checked) = badges.map_or((0, 0), |b| (b.check_items, b.check_items_checked));
+        match self.checklist {
+            None => true,
+            Some(ChecklistFilter::Present) => total > 0,
+            Some(ChecklistFilter::Absent) => total == 0,
+            Some(ChecklistFilter::Complete) => total > 0 && checked >= total,
+        }
+    }
 
-                if json {
-                    println!(
-                        "{}",
-                        serde_json::to_string(&results).context("Failed to serialize results")?
-                    );
-                } else {
-                    println!("ID\tBoard\tList\tTitle");
-                    for r in &results {
-                        println!(
-                            "{}\t{}\t{}\t{}",
-                            r.id,
-                            sanitize_field(&r.board),
-                            sanitize_field(&r.list),
-                            sanitize_field(&r.title)
-                        );
-                    }
-                }
-            }
-            CardCommands::Show {
-                card_id,
-                json,
-                comments: include_comments,
-            } => {
-                let card = client
-                    .get_card(&card_id)
-                    .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
-                let board = client
-                    .get_board(&card.id_board)
-                    .with_context(|| format!("Failed to fetch board for card '{}'", card_id))?;
-                let list = client
-                    .get_list(&card.id_list)
-                    .with_context(|| format!("Failed to fetch list for card '{}'", card_id))?;
+    fn matches_description(&self, desc: &str) -> bool {
+        let present = !desc.trim().is_empty();
+        self.has_description.is_none_or(|wanted| wanted == present)
+    }
+}
 
-                // Get board labels and filter to those on the card
-                let board_labels = client.get_board_labels(&card.id_board).with_context(|| {
-                    format!("Failed to fetch labels for board '{}'", board.name)
-                })?;
-                let labels: Vec<LabelInfo> = board_labels
-                    .into_iter()
-                    .filter(|l| card.id_labels.contains(&l.id))
-                    .map(|l| LabelInfo {
-                        name: l.name,
-                        color: l.color,
-                    })
-                    .collect();
+/// Label criteria for `card find`. Label names are compared case-insensitively.
+#[derive(Debug, Default)]
+struct LabelFilter {
+    /// Card must have every one of these labels
+    all: Vec<String>,
+    /// Card must have at least one of these labels
+    any: Vec<String>,
+    /// Card must have no labels
+    none: bool,
+}
 
-                // Fetch comments if requested
-                let comments = if include_comments {
-                    let mut actions = client.get_card_comments(&card_id).with_context(|| {
-                        format!("Failed to fetch comments for card '{}'", card_id)
-                    })?;
-                    // Reverse to get chronological order (oldest first)
-                    actions.reverse();
-                    let comment_infos: Vec<CommentInfo> = actions
-                        .into_iter()
-                        .map(|a| {
-                            let author = a
-                                .member_creator
-                                .full_name
-                                .unwrap_or(a.member_creator.username);
-                            let date = format_comment_date(&a.date);
-                            CommentInfo {
-                                date,
-                                author,
-                                text: a.data.text,
-                            }
-                        })
-                        .collect();
-                    Some(comment_infos)
-                } else {
-                    None
-                };
+impl LabelFilter {
+    fn is_empty(&self) -> bool {
+        self.all.is_empty() && self.any.is_empty() && !self.none
+    }
 
-                let result = ShowCardResult {
-                    id: card.id,
-                    name: card.name,
-                    board: board.name,
-                    list: list.name,
-                    labels,
-                    description: card.desc,
-                    archived: card.closed,
-                    comments,
-                };
+    fn matches(&self, card_labels: &[String]) -> bool {
+        let has = |name: &String| card_labels.iter().any(|l| l.eq_ignore_ascii_case(name));
+        if self.none && !card_labels.is_empty() {
+            return false;
+        }
+        self.all.iter().all(has) && (self.any.is_empty() || self.any.iter().any(has))
+    }
+}
 
-                if json {
-                    println!(
-                        "{}",
-                        serde_json::to_string(&result).context("Failed to serialize result")?
-                    );
-                } else {
-                    println!("Name: {}", result.name);
-                    println!("ID: {}", result.id);
-                    println!("Board: {}", result.board);
-                    println!("List: {}", result.list);
+/// Which boards `resolve_boards` looks up names among.
+struct BoardScope<'a> {
+    /// Trello board filter (`open`, `all`, ...)
+    filter: &'a str,
+    /// Only boards in this workspace
+    organization_id: Option<&'a str>,
+    /// Also include boards visible through workspace membership
+    include_org_boards: bool,
+}
 
-                    if result.labels.is_empty() {
-                        println!("Labels: (none)");
-                    } else {
-                        let label_strs: Vec<String> = result
-                            .labels
-                            .iter()
-                            .map(|l| match (&l.name.is_empty(), &l.color) {
-                                (false, Some(c)) => format!("{} ({})", l.name, c),
-                                (false, None) => l.name.clone(),
-                                (true, Some(c)) => format!("({})", c),
-                                (true, None) => "(no color)".to_string(),
-                            })
-                            .collect();
-                        println!("Labels: {}", label_strs.join(", "));
-                    }
+impl Default for BoardScope<'_> {
+    fn default() -> Self {
+        BoardScope {
+            filter: "open",
+            organization_id: None,
+            include_org_boards: false,
+        }
+    }
+}
 
-                    if result.archived {
-                        println!("Archived: yes");
-                    }
+/// The member's boards in `scope`, plus (with `include_org_boards`) the boards of their
+/// workspaces that they aren't a member of.
+fn scope_boards(client: &TrelloClient, scope: &BoardScope) -> Result<Vec<Board>> {
+    let mut boards = client
+        .get_member_boards_filtered(scope.filter, scope.organization_id)
+        .context("Failed to fetch boards")?;
+    if scope.include_org_boards {
+        let org_ids = match scope.organization_id {
+            Some(id) => vec![id.to_string()],
+            None => client
+                .get_member_organizations()
+                .context("Failed to fetch workspaces")?
+                .into_iter()
+                .map(|o| o.id)
+                .collect(),
+        };
+        for org_id in org_ids {
+            let org_boards = client
+                .get_organization_boards_filtered(&org_id, scope.filter)
+                .with_context(|| format!("Failed to fetch boards for workspace '{}'", org_id))?;
+            union_boards(&mut boards, org_boards);
+        }
+    }
+    Ok(boards)
+}
 
-                    if !result.description.is_empty() {
-                        println!("Description:");
-                        for line in result.description.lines() {
-                            println!("  {}", line);
-                        }
-                    }
+/// Boards to search: the board with the given ID, boards matching any of the filters
+/// (each an ID or a name substring), or all boards when no filter is given. Each board
+/// appears once, even if several filters match it. May be empty.
+///
+/// Name lookups search the boards in `scope`. `board_id` is a fast path that fetches
+/// that board directly, skipping the member boards lookup.
+fn resolve_boards(
+    client: &TrelloClient,
+    board_filters: &[String],
+    board_id: Option<&str>,
+    scope: &BoardScope,
+) -> Result<Vec<Board>> {
+    if let Some(id) = board_id {
+        let b = client
+            .get_board(id)
+            .with_context(|| format!("Board ID '{}' not found or inaccessible", id))?;
+        return Ok(vec![b]);
+    }
 
-                    if let Some(comments) = result.comments.as_ref().filter(|c| !c.is_empty()) {
-                        println!("Comments:");
-                        for c in comments {
-                            println!("  [{}] {}: {}", c.date, c.author, c.text);
-                        }
-                    }
-                }
+    let member_boards = || scope_boards(client, scope);
+
+    if board_filters.is_empty() {
+        return member_boards();
+    }
+
+    let mut boards: Vec<Board> = Vec::new();
+    let mut all_boards: Option<Vec<Board>> = None;
+    for board_filter in board_filters {
+        let matched = if looks_like_id(board_filter) {
+            vec![client.get_board(board_filter).with_context(|| {
+                format!("Board ID '{}' not found or inaccessible", board_filter)
+            })?]
+        } else {
+            if all_boards.is_none() {
+                all_boards = Some(member_boards()?);
             }
-        },
-        Commands::List { command } => match command {
-            ListCommands::Show { list_id, json } => {
-                let list = client
-                    .get_list(&list_id)
-                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
-                let board = client
-                    .get_board(&list.id_board)
-                    .with_context(|| format!("Failed to fetch board for list '{}'", list_id))?;
+            let board_filter_lower = board_filter.to_lowercase();
+            all_boards
+                .iter()
+                .flatten()
+                .filter(|b| b.name.to_lowercase().contains(&board_filter_lower))
+                .cloned()
+                .collect()
+        };
+        union_boards(&mut boards, matched);
+    }
+    Ok(boards)
+}
 
-                if json {
-                    let result = serde_json::json!({
-                        "id": list.id,
-                        "name": list.name,
-                        "board": board.name,
-                        "position": list.pos,
-                    });
-                    println!(
-                        "{}",
-                        serde_json::to_string(&result).context("Failed to serialize result")?
-                    );
-                } else {
-                    println!("Name: {}", list.name);
-                    println!("ID: {}", list.id);
-                    println!("Board: {}", board.name);
-                    println!("Position: {}", list.pos);
+/// Append the boards in `more` that aren't already in `boards`.
+fn union_boards(boards: &mut Vec<Board>, more: Vec<Board>) {
+    for b in more {
+        if !boards.iter().any(|existing| existing.id == b.id) {
+            boards.push(b);
+        }
+    }
+}
+
+/// Cards on `boards` whose name matches `matcher`, optionally restricted to lists whose
+/// name contains `list`. `filter` is the Trello card filter (`open`, `closed`, `all`).
+fn find_matching_cards(
+    client: &TrelloClient,
+    matcher: &NameMatcher,
+    card_filter: &CardFilter,
+    boards: &[Board],
+    list: Option<&str>,
+    filter: &str,
+    since_id: Option<&str>,
+) -> Result<Vec<CardResult>> {
+    let mut results: Vec<CardResult> = Vec::new();
+    let list_filter_lower = list.map(|s| s.to_lowercase());
+
+    for b in boards {
+        let cards = match since_id {
+            Some(since_id) => client.get_board_cards_since(&b.id, since_id),
+            None => client.get_board_cards_filtered(&b.id, filter),
+        }
+        .with_context(|| format!("Failed to fetch cards for board '{}'", b.name))?;
+        let lists = if card_filter.include_closed_lists {
+            client.get_board_lists_all(&b.id)
+        } else {
+            client.get_board_lists(&b.id)
+        }
+        .with_context(|| format!("Failed to fetch lists for board '{}'", b.name))?;
+        let list_map: HashMap<String, List> =
+            lists.into_iter().map(|l| (l.id.clone(), l)).collect();
+        let label_filter = &card_filter.labels;
+        let label_map: HashMap<String, String> = if label_filter.is_empty() {
+            HashMap::new()
+        } else {
+            client
+                .get_board_labels(&b.id)
+                .with_context(|| format!("Failed to fetch labels for board '{}'", b.name))?
+                .into_iter()
+                .map(|l| (l.id, l.name))
+                .collect()
+        };
+
+        for card in cards {
+            if !matcher.is_match(&card.name)
+                || !card_filter.matches_description(&card.desc)
+                || !card_filter.matches_members(&card.id_members)
+                || !card_filter.matches_checklists(card.badges.as_ref())
+            {
+                continue;
+            }
+            if !label_filter.is_empty() {
+                let card_labels: Vec<String> = card
+                    .id_labels
+                    .iter()
+                    .map(|id| label_map.get(id).unwrap_or(id).clone())
+                    .collect();
+                if !label_filter.matches(&card_labels) {
+                    continue;
                 }
             }
-            ListCommands::Move { list_id, position } => {
+            let Some(card_list) = list_map.get(&card.id_list) else {
+                continue;
+            };
+
+            // Apply list filter if specified
+            if list_filter_lower
+                .as_ref()
+                .is_some_and(|filter_lower| !card_list.name.to_lowercase().contains(filter_lower))
+            {
+                continue;
+            }
+
+            results.push(CardResult {
+                id: card.id,
+                board: b.name.clone(),
+                list: card_list.name.clone(),
+                title: card.name,
+                board_id: b.id.clone(),
+                list_id: card.id_list,
+                closed: card.closed,
+                list_closed: card_list.closed,
+                pos: None,
+                labels: None,
+                url: card.short_url,
+                description: Some(card.desc),
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Index of the card in `cards` with ID `query`, or else the only card whose name
+/// contains `query` (case-insensitive).
+fn find_reference_card(cards: &[Card], query: &str) -> Result<usize> {
+    let items: Vec<(&str, &str)> = cards
+        .iter()
+        .map(|c| (c.id.as_str(), c.name.as_str()))
+        .collect();
+    find_reference(&items, query, "card", "in the list")
+}
+
+/// Index of the list in `lists` with ID `query`, or else the only list whose name
+/// contains `query` (case-insensitive).
+fn find_reference_list(lists: &[List], query: &str) -> Result<usize> {
+    let items: Vec<(&str, &str)> = lists
+        .iter()
+        .map(|l| (l.id.as_str(), l.name.as_str()))
+        .collect();
+    find_reference(&items, query, "list", "on the board")
+}
+
+/// Index of the `(id, name)` item with ID `query`, or else the only one whose name
+/// contains `query`. `kind` and `place` word the errors, e.g. "card" and "in the list".
+fn find_reference(items: &[(&str, &str)], query: &str, kind: &str, place: &str) -> Result<usize> {
+    if let Some(index) = items.iter().position(|(id, _)| *id == query) {
+        return Ok(index);
+    }
+    let query_lower = query.to_lowercase();
+    let matches: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, name))| name.to_lowercase().contains(&query_lower))
+        .map(|(i, _)| i)
+        .collect();
+    match matches[..] {
+        [] => {
+            Err(TrelloError::NotFound(format!("No {} matching '{}' {}", kind, query, place)).into())
+        }
+        [index] => Ok(index),
+        _ => {
+            let options = matches
+                .iter()
+                .map(|&i| format!("{} ({})", items[i].1, items[i].0))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(TrelloError::User(format!(
+                "Multiple {}s match '{}': {}. Use the {} ID instead.",
+                kind, query, options, kind
+            ))
+            .into())
+        }
+    }
+}
+
+/// The first card whose name equals `name`, ignoring case.
+fn find_duplicate_card<'a>(cards: &'a [Card], name: &str) -> Option<&'a Card> {
+    let name = name.to_lowercase();
+    cards.iter().find(|c| c.name.to_lowercase() == name)
+}
+
+/// Options for `card show` that apply to every card shown.
+struct ShowOptions {
+    board: Option<String>,
+    compact: bool,
+    include_comments: bool,
+    since: Option<NaiveDate>,
+    last_n_comments: Option<usize>,
+    render_markdown: bool,
+    raw_description: bool,
+    show_position: bool,
+    board_context: bool,
+    webhooks: bool,
+    highlight_comments: Option<Regex>,
+}
+
+/// A card fetched for `card show`: the `card find` schema for --compact, or the full
+/// details together with the cards in its list (for --show-position and --board-context).
+enum ShownCard {
+    Compact(Box<CardResult>),
+    Full {
+        result: Box<ShowCardResult>,
+        list_cards: Vec<Card>,
+    },
+}
+
+/// The error for a `card show` of several cards where some failed: NotFound when every
+/// failure was NotFound, and otherwise the category of the first failure.
+fn show_failures_error(failures: Vec<anyhow::Error>, total: usize) -> anyhow::Error {
+    let message = format!("Failed to show {} of {} cards", failures.len(), total);
+    if failures
+        .iter()
+        .all(|e| error::exit_code(e) == ExitCode::NotFound)
+    {
+        return TrelloError::NotFound(message).into();
+    }
+    // Keep the first failure in the chain so its category sets the exit code
+    match failures.into_iter().next() {
+        Some(first) => first.context(message),
+        None => anyhow::anyhow!(message),
+    }
+}
+
+/// Resolve `card_id` and fetch everything `card show` displays for it.
+fn fetch_shown_card(client: &TrelloClient, card_id: &str, opts: &ShowOptions) -> Result<ShownCard> {
+    let card_id = client
+        .resolve_card(card_id, opts.board.as_deref())
+        .with_context(|| format!("Failed to resolve card '{}'", card_id))?;
+    let card = client
+        .get_card(&card_id)
+        .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
+    let board = client
+        .get_board(&card.id_board)
+        .with_context(|| format!("Failed to fetch board for card '{}'", card_id))?;
+    let list = client
+        .get_list(&card.id_list)
+        .with_context(|| format!("Failed to fetch list for card '{}'", card_id))?;
+
+    if opts.compact {
+        let result = CardResult {
+            id: card.id,
+            board: board.name,
+            list: list.name,
+            title: card.name,
+            board_id: card.id_board,
+            list_id: card.id_list,
+            closed: card.closed,
+            list_closed: list.closed,
+            pos: None,
+            labels: None,
+            url: card.short_url,
+            description: None,
+        };
+        return Ok(ShownCard::Compact(Box::new(result)));
+    }
+
+    // Get board labels and filter to those on the card
+    let board_labels = client
+        .get_board_labels(&card.id_board)
+        .with_context(|| format!("Failed to fetch labels for board '{}'", board.name))?;
+    let labels: Vec<LabelInfo> = board_labels
+        .into_iter()
+        .filter(|l| card.id_labels.contains(&l.id))
+        .map(|l| LabelInfo {
+            name: l.name,
+            color: l.color,
+        })
+        .collect();
+
+    // Fetch comments if requested
+    let comments = if opts.include_comments {
+        let mut actions = client
+            .get_card_comments(&card_id)
+            .with_context(|| format!("Failed to fetch comments for card '{}'", card_id))?;
+        // Reverse to get chronological order (oldest first)
+        actions.reverse();
+        let comment_infos: Vec<CommentInfo> =
+            filter_comments(actions, opts.since, opts.last_n_comments)
+                .into_iter()
+                .map(|a| {
+                    let author = a
+                        .member_creator
+                        .full_name
+                        .unwrap_or(a.member_creator.username);
+                    let date = format_comment_date(&a.date);
+                    let highlight_match = opts
+                        .highlight_comments
+                        .as_ref()
+                        .map(|re| re.is_match(&a.data.text));
+                    CommentInfo {
+                        id: a.id,
+                        date,
+                        author,
+                        text: a.data.text,
+                        highlight_match,
+                    }
+                })
+                .collect();
+        Some(comment_infos)
+    } else {
+        None
+    };
+
+    let list_cards = if opts.show_position || opts.board_context {
+        client
+            .get_list_cards(&card.id_list)
+            .with_context(|| format!("Failed to fetch cards for list '{}'", list.name))?
+    } else {
+        Vec::new()
+    };
+    let position = if opts.show_position {
+        card_ordinal(&list_cards, &card.id)
+    } else {
+        None
+    };
+    let siblings = opts
+        .board_context
+        .then(|| list_siblings(&list_cards, &card.id));
+
+    let webhooks = if opts.webhooks {
+        let webhooks = client
+            .get_token_webhooks()
+            .context("Failed to fetch webhooks")?;
+        Some(
+            webhooks
+                .into_iter()
+                .filter(|w| w.id_model == card.id)
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    // Only look up the current member when there are votes to check
+    let has_voted = if card.id_members_voted.is_empty() {
+        Some(false)
+    } else {
+        let me = client
+            .get_current_member()
+            .context("Failed to fetch current member")?;
+        Some(card.id_members_voted.contains(&me.id))
+    };
+
+    let result = ShowCardResult {
+        id: card.id,
+        name: card.name,
+        board: board.name,
+        list: list.name,
+        labels,
+        description: card.desc,
+        start: card.start,
+        archived: card.closed,
+        cover_color: card.cover.and_then(|c| c.color),
+        vote_count: card.badges.map(|b| b.votes),
+        has_voted,
+        due: card.due,
+        last_activity: card.date_last_activity.as_deref().map(format_comment_date),
+        url: card.url,
+        position,
+        comments,
+        siblings,
+        webhooks,
+        short_url: card.short_url,
+    };
+    Ok(ShownCard::Full {
+        result: Box::new(result),
+        list_cards,
+    })
+}
+
+/// Fields that `card show --field` can select.
+const SHOW_FIELDS: [&str; 9] = [
+    "id",
+    "name",
+    "board",
+    "list",
+    "labels",
+    "description",
+    "archived",
+    "due",
+    "url",
+];
+
+/// The requested `--field` names that `card show` knows, in order and without repeats,
+/// warning about the rest.
+fn known_show_fields(requested: &[String]) -> Vec<&str> {
+    let mut fields: Vec<&str> = Vec::new();
+    for name in requested {
+        if !SHOW_FIELDS.contains(&name.as_str()) {
+            eprintln!(
+                "Warning: ignoring unknown field '{}' (valid fields: {})",
+                name,
+                SHOW_FIELDS.join(", ")
+            );
+        } else if !fields.contains(&name.as_str()) {
+            fields.push(name);
+        }
+    }
+    fields
+}
+
+/// The named fields of a card's JSON representation, in the order given.
+fn select_fields(
+    result: &ShowCardResult,
+    fields: &[&str],
+) -> Result<Vec<(String, serde_json::Value)>> {
+    let serde_json::Value::Object(mut all) =
+        serde_json::to_value(result).context("Failed to serialize result")?
+    else {
+        unreachable!("ShowCardResult serializes to an object");
+    };
+    Ok(fields
+        .iter()
+        .map(|name| {
+            let value = all.remove(*name).unwrap_or(serde_json::Value::Null);
+            (name.to_string(), value)
+        })
+        .collect())
+}
+
+/// A field value for `card show --field` without --json: strings unquoted, labels by
+/// name, and an empty string for missing values.
+fn field_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| match item.get("name") {
+                Some(name) => field_text(name),
+                None => field_text(item),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// One comment in `card show` output. With `--highlight-comments`, matching comments
+/// are marked with `>>>` (and bold when `ansi` is set) and the rest are dimmed.
+fn format_comment_line(comment: &CommentInfo, ansi: bool) -> String {
+    let line = format!("[{}] {}: {}", comment.date, comment.author, comment.text);
+    match comment.highlight_match {
+        None => format!("  {}", line),
+        Some(true) if ansi => format!(">>> {}{}{}", ANSI_BOLD, line, ANSI_RESET),
+        Some(true) => format!(">>> {}", line),
+        Some(false) if ansi => format!("    {}{}{}", ANSI_DIM, line, ANSI_RESET),
+        Some(false) => format!("    {}", line),
+    }
+}
+
+/// Print a card's details in the human-readable `card show` format.
+fn print_card_details(result: &ShowCardResult, list_cards: &[Card], opts: &ShowOptions) {
+    println!("Name: {}", result.name);
+    println!("ID: {}", result.id);
+    println!("Board: {}", result.board);
+    println!("List: {}", result.list);
+
+    if let Some(ref p) = result.position {
+        println!(
+            "Position: {} of {} in list '{}'",
+            p.ordinal, p.total, result.list
+        );
+        println!("Position in '{}':", result.list);
+        print!(
+            "{}",
+            render_position_context(list_cards, &result.id, POSITION_CONTEXT)
+        );
+    }
+
+    if result.labels.is_empty() {
+        println!("Labels: (none)");
+    } else {
+        let label_strs: Vec<String> = result
+            .labels
+            .iter()
+            .map(|l| match (&l.name.is_empty(), &l.color) {
+                (false, Some(c)) => format!("{} ({})", l.name, c),
+                (false, None) => l.name.clone(),
+                (true, Some(c)) => format!("({})", c),
+                (true, None) => "(no color)".to_string(),
+            })
+            .collect();
+        println!("Labels: {}", label_strs.join(", "));
+    }
+
+    if result.archived {
+        println!("Archived: yes");
+    }
+
+    if let Some(ref color) = result.cover_color {
+        println!("Cover: {}", color);
+    }
+
+    if let Some(votes) = result.vote_count.filter(|&v| v > 0) {
+        let yours = if result.has_voted == Some(true) {
+            " (including yours)"
+        } else {
+            ""
+        };
+        println!("Votes: {}{}", votes, yours);
+    }
+
+    if let Some(ref start) = result.start {
+        println!("Start: {}", format_comment_date(start));
+    }
+
+    if let Some(ref due) = result.due {
+        println!("Due: {}", format_comment_date(due));
+    }
+
+    if let Some(ref last_activity) = result.last_activity {
+        println!("Last activity: {}", last_activity);
+    }
+
+    if !result.url.is_empty() {
+        println!("URL: {}", result.url);
+    }
+
+    if !result.description.is_empty() {
+        println!("Description:");
+        if opts.render_markdown {
+            print!(
+                "{}",
+                render_markdown_to_terminal(&result.description, terminal_width())
+            );
+        } else if opts.raw_description {
+            println!("{}", result.description);
+        } else {
+            for line in result.description.lines() {
+                println!("  {}", line);
+            }
+        }
+    }
+
+    if let Some(comments) = result.comments.as_ref().filter(|c| !c.is_empty()) {
+        println!("Comments:");
+        let ansi = io::stdout().is_terminal();
+        for c in comments {
+            println!("{}", format_comment_line(c, ansi));
+        }
+    }
+
+    if let Some(ref webhooks) = result.webhooks {
+        if webhooks.is_empty() {
+            println!("Webhooks: none");
+        } else {
+            println!("Webhooks:");
+            for w in webhooks {
+                let status = if w.active { "active" } else { "inactive" };
+                let description = if w.description.is_empty() {
+                    String::new()
+                } else {
+                    format!("{} ", sanitize_field(&w.description))
+                };
+                println!(
+                    "  [{}] {}-> {} ({})",
+                    status, description, w.callback_url, w.id
+                );
+            }
+        }
+    }
+
+    if let Some(ref siblings) = result.siblings {
+        println!("Cards in list '{}':", result.list);
+        for s in siblings {
+            let marker = if s.current { ">" } else { " " };
+            let short_id: String = s.id.chars().take(8).collect();
+            println!(
+                "{} {:>3}. {}  {}",
+                marker,
+                s.ordinal,
+                short_id,
+                sanitize_field(&s.name)
+            );
+        }
+    }
+}
+
+/// Refuse to search more than `max` boards, since each board costs several API calls.
+fn check_board_limit(count: usize, max: usize) -> Result<()> {
+    if count > max {
+        return Err(TrelloError::User(format!(
+            "This search would cover {} boards (limit {}). Narrow it with --board, or pass \
+             --confirm-large (or a higher --max-boards) to search them all",
+            count, max
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// `card find --json` output: an array of results, wrapped in a pagination envelope
+/// when `limit` is set.
+fn card_results_json(
+    results: &[CardResult],
+    total: usize,
+    offset: usize,
+    limit: usize,
+) -> Result<String> {
+    let output = if limit > 0 {
+        serde_json::to_string(&serde_json::json!({
+            "total": total,
+            "offset": offset,
+            "limit": limit,
+            "items": results,
+        }))
+    } else {
+        serde_json::to_string(results)
+    };
+    output.context("Failed to serialize results")
+}
+
+/// JSON Schema (draft 7) for the output of `card find --json`: an array of card results.
+/// Hand-written to match `CardResult`; the tests check that the two agree.
+fn card_result_json_schema() -> serde_json::Value {
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "card find --json",
+        "description": "Cards matching the search. With --limit the array is \
+                        instead the \"items\" field of {\"total\", \"offset\", \"limit\", \"items\"}.",
+        "type": "array",
+        "items": { "$ref": "#/definitions/CardResult" },
+        "definitions": {
+            "CardResult": {
+                "type": "object",
+                "required": ["id", "board", "list", "title", "list_closed", "url"],
+                "additionalProperties": false,
+                "properties": {
+                    "id": { "type": "string", "description": "The card's ID" },
+                    "board": { "type": "string", "description": "Name of the card's board" },
+                    "list": { "type": "string", "description": "Name of the card's list" },
+                    "title": { "type": "string", "description": "The card's name" },
+                    "list_closed": {
+                        "type": "boolean",
+                        "description": "Whether the card's list is archived"
+                    },
+                    "pos": {
+                        "type": "number",
+                        "description": "The card's raw position within its list"
+                    },
+                    "labels": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Names of the card's labels"
+                    },
+                    "url": {
+                        "type": ["string", "null"],
+                        "description": "The card's short URL"
+                    },
+                    "description": {
+                        "type": "string",
+                        "description": "The card's description, only with --show-description"
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// JSON Schema (draft 7) for the output of `card show --json` for a single card.
+/// Hand-written to match `ShowCardResult`; the tests check that the two agree.
+fn show_card_result_json_schema() -> serde_json::Value {
+    let nullable_string = |description: &str| serde_json::json!({ "type": ["string", "null"], "description": description });
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "card show --json",
+        "description": "A card's details. When several cards are shown the output is an \
+                        array of these objects; --compact prints the card find schema instead.",
+        "type": "object",
+        "required": [
+            "id", "name", "board", "list", "labels", "description", "start", "archived",
+            "cover_color", "vote_count", "has_voted", "due", "last_activity", "url"
+        ],
+        "additionalProperties": false,
+        "properties": {
+            "id": { "type": "string", "description": "The card's ID" },
+            "name": { "type": "string", "description": "The card's name" },
+            "board": { "type": "string", "description": "Name of the card's board" },
+            "list": { "type": "string", "description": "Name of the card's list" },
+            "labels": {
+                "type": "array",
+                "description": "The card's labels",
+                "items": {
+                    "type": "object",
+                    "required": ["name", "color"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "color": { "type": ["string", "null"] }
+                    }
+                }
+            },
+            "description": { "type": "string", "description": "The card's description" },
+            "start": nullable_string("Start date (ISO 8601)"),
+            "archived": { "type": "boolean", "description": "Whether the card is archived" },
+            "cover_color": nullable_string("The card's cover color"),
+            "vote_count": {
+                "type": ["integer", "null"],
+                "minimum": 0,
+                "description": "Number of votes on the card"
+            },
+            "has_voted": {
+                "type": ["boolean", "null"],
+                "description": "Whether you have voted on the card"
+            },
+            "due": nullable_string("Due date (ISO 8601)"),
+            "last_activity": nullable_string("When the card last changed, in local time"),
+            "url": { "type": "string", "description": "The card's URL" },
+            "position": {
+                "type": "object",
+                "description": "The card's place in its list, only with --show-position",
+                "required": ["ordinal", "total", "raw"],
+                "properties": {
+                    "ordinal": { "type": "integer", "minimum": 1 },
+                    "total": { "type": "integer", "minimum": 1 },
+                    "raw": { "type": "number" }
+                }
+            },
+            "comments": {
+                "type": "array",
+                "description": "The card's comments, only with --comments",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "date", "author", "text"],
+                    "properties": {
+                        "id": { "type": "string", "description": "The comment's action ID" },
+                        "date": { "type": "string" },
+                        "author": { "type": "string" },
+                        "text": { "type": "string" },
+                        "highlight_match": {
+                            "type": "boolean",
+                            "description": "Whether the comment matches --highlight-comments"
+                        }
+                    }
+                }
+            },
+            "siblings": {
+                "type": "array",
+                "description": "The cards in the same list, only with --board-context",
+                "items": {
+                    "type": "object",
+                    "required": ["ordinal", "id", "name", "current"],
+                    "properties": {
+                        "ordinal": { "type": "integer", "minimum": 1 },
+                        "id": { "type": "string" },
+                        "name": { "type": "string" },
+                        "current": { "type": "boolean" }
+                    }
+                }
+            },
+            "webhooks": {
+                "type": "array",
+                "description": "Webhooks watching the card, only with --webhooks",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "description", "callbackURL", "idModel", "active"],
+                    "properties": {
+                        "id": { "type": "string" },
+                        "description": { "type": "string" },
+                        "callbackURL": { "type": "string" },
+                        "idModel": { "type": "string" },
+                        "active": { "type": "boolean" }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Drop results in lists whose name contains any of `excluded` (case-insensitive).
+fn exclude_lists(results: Vec<CardResult>, excluded: &[String]) -> Vec<CardResult> {
+    let excluded: Vec<String> = excluded.iter().map(|s| s.to_lowercase()).collect();
+    results
+        .into_iter()
+        .filter(|r| {
+            let list = r.list.to_lowercase();
+            !excluded.iter().any(|e| list.contains(e.as_str()))
+        })
+        .collect()
+}
+
+/// Keep the first `limit` results from each board, in their original order.
+/// Boards are told apart by ID, so boards that share a name are capped separately.
+/// Returns the kept results and the names of boards that were capped.
+fn cap_per_board(results: Vec<CardResult>, limit: usize) -> (Vec<CardResult>, Vec<String>) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut capped: Vec<String> = Vec::new();
+    let mut kept = Vec::new();
+
+    for r in results {
+        let count = counts.entry(r.board_id.clone()).or_default();
+        if *count < limit {
+            kept.push(r);
+        } else if *count == limit {
+            capped.push(r.board);
+        }
+        *count += 1;
+    }
+
+    (kept, capped)
+}
+
+/// Print results grouped under board and list headings. Groups are sorted by
+/// board name then list name; cards keep their order within a group.
+fn print_grouped(results: &[CardResult], w: &mut impl Write) -> io::Result<()> {
+    let mut sorted: Vec<&CardResult> = results.iter().collect();
+    sorted.sort_by(|a, b| a.board.cmp(&b.board).then_with(|| a.list.cmp(&b.list)));
+
+    let mut current: Option<(&str, &str)> = None;
+    for r in sorted {
+        if current.map(|(board, _)| board) != Some(r.board.as_str()) {
+            writeln!(w, "Board: {}", sanitize_field(&r.board))?;
+        }
+        if current != Some((r.board.as_str(), r.list.as_str())) {
+            writeln!(w, "  List: {}", list_label(r))?;
+        }
+        writeln!(w, "    {}  {}", r.id, sanitize_field(&r.title))?;
+        current = Some((&r.board, &r.list));
+    }
+    Ok(())
+}
+
+/// Header line for `card find --ndjson-with-meta`, describing the card lines that follow.
+#[derive(Serialize)]
+struct NdjsonMeta<'a> {
+    total: usize,
+    query: &'a str,
+    boards_searched: usize,
+}
+
+/// One line of `card find --ndjson-with-meta` output, tagged with `"type"`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum NdjsonLine<'a> {
+    Meta(NdjsonMeta<'a>),
+    Card(&'a CardResult),
+}
+
+/// Write one JSON object per result. With `meta`, a metadata line comes first
+/// (flushed so streaming readers see it before any cards) and every line is tagged.
+fn write_ndjson(
+    results: &[CardResult],
+    meta: Option<NdjsonMeta>,
+    w: &mut impl Write,
+) -> Result<()> {
+    let tagged = meta.is_some();
+    if let Some(meta) = meta {
+        let line = serde_json::to_string(&NdjsonLine::Meta(meta))
+            .context("Failed to serialize metadata")?;
+        writeln!(w, "{}", line)?;
+        w.flush()?;
+    }
+    for r in results {
+        let line = if tagged {
+            serde_json::to_string(&NdjsonLine::Card(r))
+        } else {
+            serde_json::to_string(r)
+        }
+        .context("Failed to serialize result")?;
+        writeln!(w, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Where `card find` writes its results: the given file (truncated, or appended
+/// to with `append`), or stdout when no path is given.
+fn output_writer(path: Option<&Path>, append: bool) -> Result<Box<dyn Write>> {
+    let Some(path) = path else {
+        return Ok(Box::new(io::stdout()));
+    };
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .with_context(|| format!("Failed to open output file {}", path.display()))?;
+    Ok(Box::new(io::BufWriter::new(file)))
+}
+
+/// Summarise a board's lists (in board order, with open card counts) and members.
+fn board_detail(board: BoardWithDetails) -> BoardDetail {
+    let mut card_counts: HashMap<&str, usize> = HashMap::new();
+    for card in &board.cards {
+        *card_counts.entry(card.id_list.as_str()).or_default() += 1;
+    }
+
+    let mut lists: Vec<&List> = board.lists.iter().collect();
+    lists.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap_or(Ordering::Equal));
+    let lists = lists
+        .into_iter()
+        .map(|l| ListSummary {
+            id: l.id.clone(),
+            name: l.name.clone(),
+            card_count: card_counts.get(l.id.as_str()).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    BoardDetail {
+        member_count: board.members.len(),
+        id: board.id,
+        name: board.name,
+        short_url: board.short_url,
+        closed: board.closed,
+        lists,
+    }
+}
+
+/// Find a board member by username (with or without a leading `@`) or full name,
+/// ignoring case.
+fn find_board_member<'a>(members: &'a [BoardMember], name: &str) -> Result<&'a BoardMember> {
+    let name = name.strip_prefix('@').unwrap_or(name);
+    members
+        .iter()
+        .find(|m| {
+            m.username.eq_ignore_ascii_case(name)
+                || m.full_name
+                    .as_deref()
+                    .is_some_and(|f| f.eq_ignore_ascii_case(name))
+        })
+        .ok_or_else(|| {
+            TrelloError::NotFound(format!("Member '{}' not found on board", name)).into()
+        })
+}
+
+/// The board label named `name`, ignoring case.
+fn find_board_label<'a>(labels: &'a [Label], name: &str) -> Result<&'a Label> {
+    labels
+        .iter()
+        .find(|l| l.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| TrelloError::NotFound(format!("Label '{}' not found on board", name)).into())
+}
+
+/// Whether any item on any of the checklists matches `regex`.
+fn has_matching_check_item(checklists: &[Checklist], regex: &Regex) -> bool {
+    checklists
+        .iter()
+        .flat_map(|c| &c.check_items)
+        .any(|item| regex.is_match(&item.name))
+}
+
+/// Whether a confirmation answer means yes.
+fn is_affirmative(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    Ok(is_affirmative(&prompt_value(prompt)?))
+}
+
+/// Lists in board order (left to right).
+fn sort_lists_by_pos(mut lists: Vec<List>) -> Vec<List> {
+    lists.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap_or(Ordering::Equal));
+    lists
+}
+
+/// Apply `action` to each list in turn, printing a line per list as it goes.
+/// Returns the number of lists processed.
+fn for_each_list(
+    lists: &[List],
+    verb: &str,
+    mut action: impl FnMut(&List) -> Result<()>,
+) -> Result<usize> {
+    for (i, l) in lists.iter().enumerate() {
+        action(l)?;
+        println!(
+            "[{}/{}] {} '{}'",
+            i + 1,
+            lists.len(),
+            verb,
+            sanitize_field(&l.name)
+        );
+    }
+    Ok(lists.len())
+}
+
+/// Label shown for a card in the interactive picker: `[Board/List] Title`.
+fn picker_label(result: &CardResult) -> String {
+    format!(
+        "[{}/{}] {}",
+        sanitize_field(&result.board),
+        list_label(result),
+        sanitize_field(&result.title)
+    )
+}
+
+/// A result's list name for human output, marked when the list is archived.
+fn list_label(result: &CardResult) -> String {
+    let name = sanitize_field(&result.list);
+    if result.list_closed {
+        format!("{} [archived]", name)
+    } else {
+        name
+    }
+}
+
+/// Slice results to the requested page; a `limit` of 0 means unlimited.
+fn paginate<T>(items: Vec<T>, offset: usize, limit: usize) -> Vec<T> {
+    let page = items.into_iter().skip(offset);
+    if limit == 0 {
+        page.collect()
+    } else {
+        page.take(limit).collect()
+    }
+}
+
+fn pagination_footer(offset: usize, shown: usize, total: usize) -> String {
+    if shown == 0 {
+        format!("Showing 0 of {}", total)
+    } else {
+        format!("Showing {}–{} of {}", offset + 1, offset + shown, total)
+    }
+}
+
+/// Render a `card show` template, replacing `{field}` placeholders with card values.
+fn render_card_template(template: &str, result: &ShowCardResult) -> String {
+    let labels = result
+        .labels
+        .iter()
+        .map(|l| l.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let archived = result.archived.to_string();
+    let replacements = [
+        ("{id}", result.id.as_str()),
+        ("{name}", result.name.as_str()),
+        ("{board}", result.board.as_str()),
+        ("{list}", result.list.as_str()),
+        ("{description}", result.description.as_str()),
+        ("{labels}", labels.as_str()),
+        ("{archived}", archived.as_str()),
+        ("{due}", result.due.as_deref().unwrap_or_default()),
+        ("{url}", result.url.as_str()),
+    ];
+
+    // Single pass so substituted values containing placeholders are not expanded again
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match replacements.iter().find(|(p, _)| tail.starts_with(p)) {
+            Some((placeholder, value)) => {
+                out.push_str(value);
+                rest = &tail[placeholder.len()..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_ITALIC: &str = "\x1b[3m";
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Terminal width from `$COLUMNS`, falling back to 80.
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .filter(|&w| w > 0)
+        .unwrap_or(80)
+}
+
+/// Number of characters a string occupies on screen, ignoring ANSI escape sequences.
+fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Replace `**bold**` and `*italic*` spans with ANSI styles. Unmatched markers are left as-is.
+fn render_inline_markdown(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('*') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let (marker, style) = if after.starts_with("**") {
+            ("**", ANSI_BOLD)
+        } else {
+            ("*", ANSI_ITALIC)
+        };
+        let inner = &after[marker.len()..];
+        match inner.find(marker) {
+            Some(end) if end > 0 => {
+                out.push_str(style);
+                out.push_str(&inner[..end]);
+                out.push_str(ANSI_RESET);
+                rest = &inner[end + marker.len()..];
+            }
+            _ => {
+                out.push_str(marker);
+                rest = inner;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Word-wrap text to `width` visible columns. The first line starts with `prefix`;
+/// continuation lines are indented to the same width.
+fn wrap_line(text: &str, width: usize, prefix: &str) -> Vec<String> {
+    let indent = " ".repeat(visible_len(prefix));
+    let mut lines = Vec::new();
+    let mut current = prefix.to_string();
+    let mut current_has_words = false;
+    for word in text.split_whitespace() {
+        if current_has_words && visible_len(&current) + 1 + visible_len(word) > width {
+            lines.push(std::mem::replace(&mut current, indent.clone()));
+            current_has_words = false;
+        }
+        if current_has_words {
+            current.push(' ');
+        }
+        current.push_str(word);
+        current_has_words = true;
+    }
+    if current_has_words {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Render `url` as a QR code in Unicode half blocks, two module rows per line, with a
+/// two-module quiet zone. Light modules are drawn as blocks so the code reads correctly
+/// on a dark terminal background. A Trello short URL gives a code about 33 columns wide.
+fn render_qr_to_string(url: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(url.as_bytes())
+        .with_context(|| format!("Failed to encode '{}' as a QR code", url))?;
+    let width = code.width();
+    let colors = code.to_colors();
+    const QUIET: usize = 2;
+    let size = width + 2 * QUIET;
+    // Whether the module at (x, y), counting the quiet zone, is drawn as a block
+    let lit = |x: usize, y: usize| {
+        if x < QUIET || y < QUIET || x >= width + QUIET || y >= width + QUIET {
+            return true;
+        }
+        colors[(y - QUIET) * width + (x - QUIET)] == qrcode::Color::Light
+    };
+
+    let mut out = String::new();
+    for y in (0..size).step_by(2) {
+        for x in 0..size {
+            let top = lit(x, y);
+            let bottom = y + 1 >= size || lit(x, y + 1);
+            out.push(match (top, bottom) {
+                (true, true) => '█',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (false, false) => ' ',
+            });
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Render a Markdown description for terminal display.
+///
+/// Handles `# headers`, `**bold**`, `*italic*`, fenced code blocks, and `- ` lists.
+fn render_markdown_to_terminal(md: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut in_code_block = false;
+
+    for line in md.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            out.push_str(&format!("    {}{}{}\n", ANSI_DIM, line, ANSI_RESET));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            out.push('\n');
+        } else if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            out.push_str(&format!("{}{}{}\n", ANSI_BOLD, heading, ANSI_RESET));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            for wrapped in wrap_line(&render_inline_markdown(item), width, "  • ") {
+                out.push_str(&wrapped);
+                out.push('\n');
+            }
+        } else {
+            for wrapped in wrap_line(&render_inline_markdown(trimmed), width, "") {
+                out.push_str(&wrapped);
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+/// Cards to move out of a list, in their current order, optionally limited to one label.
+fn plan_list_moves(mut cards: Vec<Card>, label_id: Option<&str>) -> Vec<Card> {
+    cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+    cards
+        .into_iter()
+        .filter(|c| label_id.is_none_or(|id| c.id_labels.iter().any(|l| l == id)))
+        .collect()
+}
+
+#[derive(Subcommand)]
+enum ListCommands {
+    /// Show detailed information about a list
+    Show {
+        /// The list ID or list name substring (searched across all your boards without -b)
+        list: String,
+        /// Filter by board name or ID when resolving list names
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Find lists whose name matches a pattern
+    Find {
+        /// Regex pattern to match list names
+        pattern: String,
+        /// Filter by board name or ID
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List all cards in a list
+    Cards {
+        /// The list ID or list name substring
+        list: String,
+        /// Filter by board name or ID when resolving list names
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Include archived cards
+        #[arg(long)]
+        archived: bool,
+        /// Sort cards by this field
+        #[arg(long, value_enum, default_value_t = SortField::Pos)]
+        sort: SortField,
+        /// Reverse the sort order
+        #[arg(long)]
+        sort_desc: bool,
+        /// Put cards missing the sort field first instead of last
+        #[arg(long)]
+        nulls_first: bool,
+    },
+    /// Create a list on a board
+    Create {
+        /// The board ID or board name substring
+        board: String,
+        /// The new list's name
+        name: String,
+        /// Position: "top", "bottom", or a numeric value [default: bottom]
+        #[arg(short, long, conflicts_with_all = ["after_list", "before_list", "copy_from"])]
+        position: Option<String>,
+        /// Place the list directly after this list on the board (ID or name substring)
+        #[arg(long, value_name = "LIST", conflicts_with_all = ["before_list", "copy_from"])]
+        after_list: Option<String>,
+        /// Place the list directly before this list on the board (ID or name substring)
+        #[arg(long, value_name = "LIST", conflicts_with = "copy_from")]
+        before_list: Option<String>,
+        /// Give the list the same position as this list, e.g. to replace a misspelled one
+        #[arg(long, value_name = "LIST")]
+        copy_from: Option<String>,
+    },
+    /// Change a list's position
+    Move {
+        /// The list ID
+        list_id: String,
+        /// Position: "top", "bottom", or a numeric value
+        position: String,
+    },
+    /// Move all cards from one list to another, preserving their order
+    MoveCards {
+        /// Source list ID or list name substring
+        src_list: String,
+        /// Destination list ID or list name substring
+        dst_list: String,
+        /// Filter by board name or ID when resolving list names
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Only move cards with this label
+        #[arg(long)]
+        filter_label: Option<String>,
+        /// Show what would be moved without moving anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Move without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Permanently delete every archived card in a list. This cannot be undone
+    DeleteArchivedCards {
+        /// The list ID or list name substring
+        list_id: String,
+        /// Filter by board name or ID when resolving list names
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Copy the open lists of a template board onto another board, in order
+    CreateFromTemplate {
+        /// The template board ID or board name substring
+        template_board: String,
+        /// The destination board ID or board name substring
+        dest_board: String,
+        /// Also copy each list's open cards
+        #[arg(long)]
+        include_cards: bool,
+    },
+    /// Copy a single list, open or archived, to the bottom of another board
+    Copy {
+        /// The source list ID or list name substring
+        src_list: String,
+        /// The destination board ID or board name substring
+        dst_board: String,
+        /// Filter by board name or ID when resolving the source list name
+        #[arg(short, long)]
+        board: Option<String>,
+        /// Name for the new list (default: the source list's name)
+        #[arg(long, value_name = "NAME")]
+        new_name: Option<String>,
+        /// Also copy the list's open cards, in order
+        #[arg(long)]
+        include_cards: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the config file path, whether or not it exists
+    Path,
+    /// Open the config file in $VISUAL or $EDITOR, creating it from a template if needed
+    Edit,
+    /// Check the config file, credentials and API access, printing [OK] or [FAIL] for each
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum BoardCommands {
+    /// Show detailed information about a board
+    Show {
+        /// The board ID or board name substring
+        board: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// List each open list with its open card count
+        #[arg(long)]
+        lists: bool,
+    },
+    /// List your open boards
+    List {
+        /// Show only the N most recently active boards
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        recent: Option<u32>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Print only each board's name and URL
+        #[arg(long, conflicts_with = "json")]
+        url: bool,
+        /// Sort boards by this field (ascending unless --sort-desc)
+        #[arg(long, value_enum)]
+        sort: Option<BoardSortField>,
+        /// Reverse the sort order
+        #[arg(long, requires = "sort")]
+        sort_desc: bool,
+        /// Fetch and show each board's open card count (one request per board)
+        #[arg(long, conflicts_with = "url")]
+        with_card_counts: bool,
+    },
+    /// Find boards whose name matches a pattern
+    Find {
+        /// Regex pattern to match board names
+        pattern: String,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Include closed boards
+        #[arg(long)]
+        closed: bool,
+    },
+    /// Show a board's recent activity, newest first
+    Activity {
+        /// The board ID or board name substring
+        board: String,
+        /// Show at most N actions
+        #[arg(long, value_name = "N", default_value_t = 50, value_parser = clap::value_parser!(u32).range(1..))]
+        limit: u32,
+        /// Output the actions as JSON
+        #[arg(long)]
+        json: bool,
+        /// Only show these action types, comma-separated (e.g. "createCard,commentCard")
+        #[arg(long, value_name = "TYPES")]
+        filter: Option<String>,
+    },
+    /// List and manage board membership
+    Members {
+        #[command(subcommand)]
+        command: BoardMemberCommands,
+    },
+    /// Archive every open list on a board, leaving the board itself open
+    ArchiveAllLists {
+        /// The board ID or board name substring
+        board: String,
+        /// Archive without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Restore every archived list on a board
+    RestoreAllLists {
+        /// The board ID or board name substring
+        board: String,
+    },
+    /// Close a board
+    Close {
+        /// The board ID or board name substring
+        board: String,
+        /// Close without asking for confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Reopen a closed board
+    Reopen {
+        /// The board ID or closed board name substring
+        board: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum BoardMemberCommands {
+    /// List the members of a board
+    List {
+        /// The board ID or board name substring
+        board: String,
+    },
+    /// Invite a member to a board by email
+    Invite {
+        /// The board ID or board name substring
+        board: String,
+        /// Email address of the person to invite
+        email: String,
+        /// Role on the board
+        #[arg(long, value_parser = ["normal", "admin", "observer"])]
+        role: Option<String>,
+    },
+}
+
+/// Outcome of a successfully executed command, mapped to the process exit code.
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Success,
+    /// A search completed but matched nothing (exit code 2)
+    NoMatches,
+}
+
+impl Status {
+    fn code(&self) -> ExitCode {
+        match self {
+            Status::Success => ExitCode::Success,
+            Status::NoMatches => ExitCode::NotFound,
+        }
+    }
+}
+
+/// Report an empty search result. Prints `message` to stderr and exits 2,
+/// unless `quiet_on_empty` is set, in which case it stays silent and exits 0.
+fn no_matches(message: &str, quiet_on_empty: bool) -> Status {
+    if quiet_on_empty {
+        Status::Success
+    } else {
+        eprintln!("{}", message);
+        Status::NoMatches
+    }
+}
+
+fn main() {
+    match run() {
+        Ok(status) => std::process::exit(status.code() as i32),
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            std::process::exit(error::exit_code(&e) as i32);
+        }
+    }
+}
+
+/// Longest card name the Trello API accepts, in characters.
+const TRELLO_MAX_NAME_LENGTH: usize = 16384;
+
+/// Card names longer than this get a warning, since they are unwieldy in the Trello UI.
+const DEFAULT_MAX_NAME_LENGTH: usize = 512;
+
+/// Check a new card name before sending it: it must not be blank or longer than the
+/// API allows. Names longer than `max_len` are allowed with a warning.
+fn validate_card_name(name: &str, max_len: usize) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(TrelloError::User("card name cannot be empty".to_string()).into());
+    }
+    let len = name.chars().count();
+    if len > TRELLO_MAX_NAME_LENGTH {
+        return Err(TrelloError::User(format!(
+            "card name is {} characters long; Trello allows at most {}",
+            len, TRELLO_MAX_NAME_LENGTH
+        ))
+        .into());
+    }
+    if len > max_len {
+        eprintln!(
+            "Warning: card name is {} characters long (more than {})",
+            len, max_len
+        );
+    }
+    Ok(())
+}
+
+/// The new name given to `card update` by `--name`, `--name-from-file` or
+/// `--name-from-stdin`. Names read from a file or stdin use only their first line.
+fn read_card_name(
+    name: Option<String>,
+    from_file: Option<&Path>,
+    from_stdin: bool,
+    mut stdin: impl BufRead,
+) -> Result<Option<String>> {
+    let text = if let Some(path) = from_file {
+        read_file_value(path)?
+    } else if from_stdin {
+        let mut line = String::new();
+        stdin
+            .read_line(&mut line)
+            .context("Failed to read from stdin")?;
+        line
+    } else {
+        return Ok(name);
+    };
+    Ok(Some(
+        text.lines().next().unwrap_or_default().trim().to_string(),
+    ))
+}
+
+/// Resolve a flag value that may name its source: `-` reads from `stdin`,
+/// `@path` reads the file at `path`, and anything else is used literally.
+fn read_value_from_source(value: &str, mut stdin: impl Read) -> Result<String> {
+    if value == "-" {
+        let mut buf = String::new();
+        stdin
+            .read_to_string(&mut buf)
+            .context("Failed to read from stdin")?;
+        Ok(buf)
+    } else if let Some(path) = value.strip_prefix('@') {
+        read_file_value(Path::new(path))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Read a flag value from a file, which must be UTF-8 text.
+fn read_file_value(path: &Path) -> Result<String> {
+    let bytes =
+        fs::read(path).with_context(|| format!("Failed to read file '{}'", path.display()))?;
+    String::from_utf8(bytes).map_err(|_| {
+        TrelloError::User(format!("File '{}' is not valid UTF-8 text", path.display())).into()
+    })
+}
+
+/// Actions requested per page by a `watch` poll; a busy interval takes several pages.
+const WATCH_PAGE_SIZE: usize = 50;
+
+/// Actions fetched by one `watch` poll that should be printed, oldest first.
+/// Moves `cursor` to the newest of them.
+fn take_new_actions(mut actions: Vec<Action>, cursor: &mut Option<String>) -> Vec<Action> {
+    if let Some(newest) = actions.first() {
+        *cursor = Some(newest.id.clone());
+    }
+    actions.reverse();
+    actions
+}
+
+/// Sleep for `duration`, waking early once `stop` is set.
+fn sleep_until_stopped(duration: Duration, stop: &AtomicBool) {
+    let step = Duration::from_millis(100);
+    let mut slept = Duration::ZERO;
+    while slept < duration && !stop.load(AtomicOrdering::Relaxed) {
+        let nap = step.min(duration - slept);
+        std::thread::sleep(nap);
+        slept += nap;
+    }
+}
+
+/// One line of `board activity`: `[date] author: type on card 'name'`.
+fn format_action_line(action: &Action) -> String {
+    let author = action
+        .member_creator
+        .full_name
+        .as_deref()
+        .unwrap_or(&action.member_creator.username);
+    let target = match (&action.data.card, &action.data.list) {
+        (Some(card), _) => format!(" on card '{}'", sanitize_field(&card.name)),
+        (None, Some(list)) => format!(" on list '{}'", sanitize_field(&list.name)),
+        (None, None) => String::new(),
+    };
+    format!(
+        "[{}] {}: {}{}",
+        format_comment_date(&action.date),
+        author,
+        action.action_type,
+        target
+    )
+}
+
+/// The API calls `config validate` makes, so the checks can be tested without HTTP.
+trait AccountApi {
+    fn current_member(&self) -> Result<Member>;
+    fn member_boards(&self) -> Result<Vec<Board>>;
+}
+
+impl AccountApi for TrelloClient {
+    fn current_member(&self) -> Result<Member> {
+        self.get_current_member()
+    }
+
+    fn member_boards(&self) -> Result<Vec<Board>> {
+        self.get_member_boards()
+    }
+}
+
+/// The outcome of one `config validate` check.
+struct Check {
+    ok: bool,
+    message: String,
+}
+
+impl Check {
+    fn pass(message: String) -> Self {
+        Check { ok: true, message }
+    }
+
+    fn fail(message: String) -> Self {
+        Check { ok: false, message }
+    }
+}
+
+/// Run the `config validate` checks in order: the config file, the credentials, a live
+/// `/members/me` call and the accessible boards. Checks that depend on a failed one are
+/// skipped. `connect` builds the API client from the loaded credentials.
+fn validate_setup<A: AccountApi>(
+    path: &Path,
+    source: &dyn CredentialSource,
+    connect: impl FnOnce(&Config) -> Result<A>,
+) -> Vec<Check> {
+    let mut checks = Vec::new();
+    let from_env =
+        source.get("TRELLO_API_KEY").is_some() && source.get("TRELLO_API_TOKEN").is_some();
+
+    checks.push(match Config::check_file(path) {
+        Ok(true) => Check::pass(format!("Config file {} is valid", path.display())),
+        Ok(false) if from_env => Check::pass(format!(
+            "No config file at {}; using environment variables",
+            path.display()
+        )),
+        Ok(false) => Check::fail(format!("Config file {} not found", path.display())),
+        Err(e) => Check::fail(format!("{:#}", e)),
+    });
+
+    let config = match Config::load_from_source(source, path.to_path_buf()) {
+        Ok(config) if config.api_key().is_empty() || config.api_token().is_empty() => {
+            checks.push(Check::fail(
+                "TRELLO_API_KEY and TRELLO_API_TOKEN must not be empty".to_string(),
+            ));
+            return checks;
+        }
+        Ok(config) => config,
+        Err(e) => {
+            checks.push(Check::fail(format!("{:#}", e)));
+            return checks;
+        }
+    };
+    let origin = if from_env {
+        "environment variables"
+    } else {
+        "config file"
+    };
+    checks.push(Check::pass(format!(
+        "API key and token are set ({})",
+        origin
+    )));
+
+    let api = match connect(&config) {
+        Ok(api) => api,
+        Err(e) => {
+            checks.push(Check::fail(format!("Failed to create API client: {:#}", e)));
+            return checks;
+        }
+    };
+    match api.current_member() {
+        Ok(member) => checks.push(Check::pass(format!(
+            "Authenticated as {} (@{})",
+            member.full_name.as_deref().unwrap_or(&member.username),
+            member.username
+        ))),
+        Err(e) => {
+            checks.push(Check::fail(format!("Failed to authenticate: {:#}", e)));
+            return checks;
+        }
+    }
+
+    checks.push(match api.member_boards() {
+        Ok(boards) => Check::pass(format!("{} accessible boards", boards.len())),
+        Err(e) => Check::fail(format!("Failed to fetch boards: {:#}", e)),
+    });
+    checks
+}
+
+/// The body of a reply to the comment `action`: `text` prefixed with a mention of its author.
+fn format_reply(action: &Action, text: &str) -> Result<String> {
+    if action.action_type != "commentCard" {
+        return Err(TrelloError::User(format!(
+            "Action '{}' is a {} action, not a comment",
+            action.id, action.action_type
+        ))
+        .into());
+    }
+    Ok(format!("@{} {}", action.member_creator.username, text))
+}
+
+/// The first 100 characters of a description on one line, for the `card find` table.
+fn description_preview(desc: &str) -> String {
+    const MAX_CHARS: usize = 100;
+    let flat = sanitize_field(desc.trim());
+    if flat.chars().count() > MAX_CHARS {
+        let start: String = flat.chars().take(MAX_CHARS).collect();
+        format!("{}...", start)
+    } else {
+        flat
+    }
+}
+
+/// The start of a comment on one line, for confirmation messages.
+fn comment_preview(text: &str) -> String {
+    const MAX_CHARS: usize = 50;
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() > MAX_CHARS {
+        let start: String = flat.chars().take(MAX_CHARS).collect();
+        format!("{}...", start.trim_end())
+    } else {
+        flat
+    }
+}
+
+fn prompt_value(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut value = String::new();
+    io::stdin()
+        .read_line(&mut value)
+        .context("Failed to read input")?;
+    Ok(value.trim().to_string())
+}
+
+/// Best-effort attempt to open `url` in the user's browser.
+fn open_in_browser(url: &str) -> bool {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        // `cmd /C start` would split the URL at `&`; the URL protocol handler takes it verbatim.
+        let mut c = std::process::Command::new("rundll32");
+        c.arg("url.dll,FileProtocolHandler");
+        c
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Open `path` in the user's editor and wait for it to exit.
+fn open_in_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+
+    // Allow editors with arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+/// Run the OAuth 1.0a out-of-band flow and return the access token.
+fn oauth_login(api_key: &str, api_secret: &str) -> Result<String> {
+    let oauth = oauth::OAuthClient::new(api_key, api_secret);
+    let request_token = oauth.get_request_token()?;
+    let url = oauth.authorize_url(&request_token, "trello-cli");
+
+    println!("Authorize trello-cli by visiting:\n\n  {}\n", url);
+    if open_in_browser(&url) {
+        println!("(Opened in your browser)");
+    }
+    let verifier = prompt_value("Verification code: ")?;
+    if verifier.is_empty() {
+        return Err(TrelloError::User("No verification code entered".to_string()).into());
+    }
+
+    Ok(oauth.get_access_token(&request_token, &verifier)?.token)
+}
+
+fn run() -> Result<Status> {
+    // clap exits with 2 on usage errors, which would read as "not found"
+    let cli = Cli::try_parse().unwrap_or_else(|e| {
+        let code = if e.use_stderr() {
+            ExitCode::UserError
+        } else {
+            ExitCode::Success
+        };
+        let _ = e.print();
+        std::process::exit(code as i32);
+    });
+
+    if let Commands::Login {
+        api_key,
+        api_token,
+        verify,
+        oauth,
+        api_secret,
+    } = cli.command
+    {
+        let key = match api_key {
+            Some(k) => k,
+            None => prompt_value("API key: ")?,
+        };
+        let token = if oauth {
+            let secret = match api_secret {
+                Some(s) => s,
+                None => rpassword::prompt_password("API secret: ")
+                    .context("Failed to read API secret")?,
+            };
+            oauth_login(&key, &secret)?
+        } else {
+            match api_token {
+                Some(t) => t,
+                None => {
+                    rpassword::prompt_password("API token: ").context("Failed to read API token")?
+                }
+            }
+        };
+        let member = if verify {
+            let client = TrelloClient::new(&Config::new(&key, &token));
+            let member = client
+                .get_current_member()
+                .context("Failed to verify credentials; nothing was saved")?;
+            Some(member)
+        } else {
+            None
+        };
+        Config::save(&key, &token)?;
+        let path = Config::config_path()?;
+        println!("Credentials saved to {}", path.display());
+        if let Some(member) = member {
+            println!(
+                "Logged in as {} (@{})",
+                member.full_name.as_deref().unwrap_or(&member.username),
+                member.username
+            );
+        }
+        return Ok(Status::Success);
+    }
+
+    if let Commands::Config { command } = &cli.command {
+        let path = Config::config_path()?;
+        match command {
+            ConfigCommands::Path => println!("{}", path.display()),
+            ConfigCommands::Edit => {
+                if Config::ensure_exists(&path)? {
+                    eprintln!("Created {}", path.display());
+                }
+                open_in_editor(&path)?;
+            }
+            ConfigCommands::Validate => {
+                let checks = validate_setup(&path, &VarSource, |config| {
+                    let client = TrelloClient::new(config);
+                    match cli.timeout {
+                        Some(secs) => {
+                            client.with_timeout((secs > 0).then(|| Duration::from_secs(secs)))
+                        }
+                        None => Ok(client),
+                    }
+                });
+                for check in &checks {
+                    let status = if check.ok { "[OK]" } else { "[FAIL]" };
+                    println!("{} {}", status, check.message);
+                }
+                let failed = checks.iter().filter(|c| !c.ok).count();
+                if failed > 0 {
+                    anyhow::bail!("{} of {} checks failed", failed, checks.len());
+                }
+            }
+        }
+        return Ok(Status::Success);
+    }
+
+    // Schemas describe the output format, so they need neither credentials nor requests
+    let schema = match &cli.command {
+        Commands::Card {
+            command: CardCommands::Find {
+                json_schema: true, ..
+            },
+        } => Some(card_result_json_schema()),
+        Commands::Card {
+            command: CardCommands::Show {
+                json_schema: true, ..
+            },
+        } => Some(show_card_result_json_schema()),
+        _ => None,
+    };
+    if let Some(schema) = schema {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&schema).context("Failed to serialize schema")?
+        );
+        return Ok(Status::Success);
+    }
+
+    let config = Config::load()?;
+    // Polling has to see fresh responses, so `watch` doesn't cache
+    let enable_cache = !matches!(cli.command, Commands::Watch { .. });
+    let mut client =
+        TrelloClient::with_cache(&config, enable_cache).with_audit_logger(AuditLogger::from_env()?);
+    if let Some(secs) = cli.timeout {
+        client = client.with_timeout((secs > 0).then(|| Duration::from_secs(secs)))?;
+    }
+
+    match cli.command {
+        Commands::Login { .. } | Commands::Config { .. } => unreachable!(),
+        Commands::Whoami { json } => {
+            let member = client
+                .get_current_member()
+                .context("Failed to fetch current member")?;
+            let result = WhoamiResult {
+                id: member.id,
+                username: member.username,
+                full_name: member.full_name,
+            };
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string(&result).context("Failed to serialize result")?
+                );
+            } else {
+                println!("Username: {}", result.username);
+                if let Some(ref full_name) = result.full_name {
+                    println!("Name: {}", full_name);
+                }
+                println!("ID: {}", result.id);
+            }
+        }
+        Commands::Watch {
+            board,
+            interval_secs,
+            json,
+        } => {
+            let board = client
+                .resolve_board(&board)
+                .with_context(|| format!("Failed to resolve board '{}'", board))?;
+            // Start from the newest action so that only activity from now on is printed
+            let mut cursor = client
+                .get_board_latest_action_id(&board.id)
+                .with_context(|| format!("Failed to fetch activity for board '{}'", board.name))?;
+
+            let stop = Arc::new(AtomicBool::new(false));
+            let handler_stop = Arc::clone(&stop);
+            ctrlc::set_handler(move || handler_stop.store(true, AtomicOrdering::Relaxed))
+                .context("Failed to install the Ctrl-C handler")?;
+
+            eprintln!(
+                "Watching board '{}' every {}s (Ctrl-C to stop)",
+                board.name, interval_secs
+            );
+            loop {
+                sleep_until_stopped(Duration::from_secs(interval_secs), &stop);
+                if stop.load(AtomicOrdering::Relaxed) {
+                    break;
+                }
+                let actions = match &cursor {
+                    Some(since) => {
+                        client.get_board_actions_since(&board.id, since, WATCH_PAGE_SIZE)
+                    }
+                    None => client.get_board_actions(&board.id, WATCH_PAGE_SIZE, None),
+                };
+                let actions = match actions {
+                    Ok(actions) => actions,
+                    // Keep watching through network blips; anything else is fatal
+                    Err(e) if error::exit_code(&e) == error::ExitCode::NetworkError => {
+                        eprintln!("Warning: failed to poll board '{}': {:#}", board.name, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        return Err(e.context(format!(
+                            "Failed to fetch activity for board '{}'",
+                            board.name
+                        )));
+                    }
+                };
+                for action in take_new_actions(actions, &mut cursor) {
+                    if json {
+                        println!(
+                            "{}",
+                            serde_json::to_string(&action).context("Failed to serialize action")?
+                        );
+                    } else {
+                        println!("{}", format_action_line(&action));
+                    }
+                }
+            }
+            eprintln!("Stopped watching board '{}'", board.name);
+        }
+        Commands::Card { command } => match command {
+            CardCommands::Create {
+                list,
+                name,
+                description,
+                copy_description_from,
+                position,
+                after_card,
+                before_card,
+                board,
+                label,
+                member,
+                checklist,
+                checklist_items,
+                due,
+                start,
+                error_on_duplicate,
+                rename_duplicate,
+                template_file,
+                var,
+                quiet,
+            } => {
+                let template = match template_file {
+                    Some(path) => {
+                        let text = read_file_value(&path)?;
+                        parse_card_template(&text, &path, &var.into_iter().collect())?
+                    }
+                    None => CardTemplate::default(),
+                };
+                // Flags given on the command line override the template
+                let Some(name) = name.or(template.name) else {
+                    return Err(TrelloError::User(
+                        "A card name is required, either as an argument or in the template"
+                            .to_string(),
+                    )
+                    .into());
+                };
+                let position = position
+                    .or(template.position)
+                    .unwrap_or_else(|| "bottom".to_string());
+                let due = match (due, template.due) {
+                    (Some(date), _) => Some(date),
+                    (None, Some(text)) => Some(parse_date(&text).map_err(TrelloError::User)?),
+                    (None, None) => None,
+                };
+                let mut label = label;
+                label.extend(template.labels);
+
+                let checklists = merge_checklists(checklist, checklist_items);
+                let mut copied_from = None;
+                let description = match (description, copy_description_from) {
+                    (Some(d), source) => {
+                        if source.is_some() {
+                            eprintln!("Note: --description overrides --copy-description-from");
+                        }
+                        Some(read_value_from_source(&d, io::stdin())?)
+                    }
+                    (None, Some(source)) => {
+                        let source_id = client
+                            .resolve_card(&source, None)
+                            .with_context(|| format!("Failed to resolve card '{}'", source))?;
+                        let source_card = client
+                            .get_card(&source_id)
+                            .with_context(|| format!("Failed to fetch card '{}'", source_id))?;
+                        copied_from = Some(source_card.name);
+                        Some(source_card.desc)
+                    }
+                    (None, None) => template.description,
+                };
+                let list_id = client
+                    .resolve_list(&list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", list))?;
+
+                let mut name = name;
+                if error_on_duplicate || rename_duplicate.is_some() {
+                    let cards = client
+                        .get_list_cards(&list_id)
+                        .with_context(|| format!("Failed to fetch cards for list '{}'", list_id))?;
+                    if let Some(existing) = find_duplicate_card(&cards, &name) {
+                        match rename_duplicate {
+                            Some(ref suffix) => name = format!("{} {}", name, suffix),
+                            None => {
+                                let list = client.get_list(&list_id).with_context(|| {
+                                    format!("Failed to fetch list '{}'", list_id)
+                                })?;
+                                return Err(TrelloError::User(format!(
+                                    "card '{}' already exists in list '{}' ({})",
+                                    existing.name, list.name, existing.id
+                                ))
+                                .into());
+                            }
+                        }
+                    }
+                }
+
+                let reference = after_card
+                    .as_deref()
+                    .map(|card| (card, true))
+                    .or(before_card.as_deref().map(|card| (card, false)));
+                let pos = if let Some((reference, after)) = reference {
+                    let mut cards = client
+                        .get_list_cards(&list_id)
+                        .with_context(|| format!("Failed to fetch cards for list '{}'", list_id))?;
+                    cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+                    let index = find_reference_card(&cards, reference)?;
+                    let positions: Vec<f64> = cards.iter().map(|c| c.pos).collect();
+                    position_next_to(&positions, index, after)
+                } else {
+                    match position.as_str() {
+                        "top" | "bottom" => position.clone(),
+                        _ => {
+                            if let Ok(target_pos) = position.parse::<usize>() {
+                                let mut cards =
+                                    client.get_list_cards(&list_id).with_context(|| {
+                                        format!("Failed to fetch cards for list '{}'", list_id)
+                                    })?;
+                                cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+                                compute_position(&cards, target_pos)
+                            } else {
+                                position.clone()
+                            }
+                        }
+                    }
+                };
+
+                // Resolve label and member names before creating anything, so a typo
+                // doesn't leave a half-configured card behind
+                let list = client
+                    .get_list(&list_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
+                let mut label_ids: Vec<String> = Vec::new();
+                if !label.is_empty() {
+                    let board_labels =
+                        client.get_board_labels(&list.id_board).with_context(|| {
+                            format!("Failed to fetch labels for list '{}'", list.name)
+                        })?;
+                    for label_name in &label {
+                        let l = find_board_label(&board_labels, label_name)?;
+                        if !label_ids.contains(&l.id) {
+                            label_ids.push(l.id.clone());
+                        }
+                    }
+                }
+                let mut members: Vec<BoardMember> = Vec::new();
+                if !member.is_empty() {
+                    let board_members =
+                        client.get_board_members(&list.id_board).with_context(|| {
+                            format!("Failed to fetch members for list '{}'", list.name)
+                        })?;
+                    for member_name in &member {
+                        let m = find_board_member(&board_members, member_name)?;
+                        if !members.iter().any(|existing| existing.id == m.id) {
+                            members.push(m.clone());
+                        }
+                    }
+                }
+
+                let body = CreateCard {
+                    name,
+                    pos,
+                    id_list: list_id,
+                    desc: description,
+                    due: due.map(local_date_to_utc),
+                    start: start.map(local_date_to_utc),
+                };
+
+                let card = client.create_card(&body).context("Failed to create card")?;
+
+                for label_id in &label_ids {
+                    client
+                        .add_label_to_card(&card.id, label_id)
+                        .with_context(|| {
+                            format!(
+                                "Failed to apply labels to card '{}' ({})",
+                                card.name, card.id
+                            )
+                        })?;
+                }
+
+                let mut assigned = Vec::new();
+                for m in &members {
+                    client
+                        .assign_member_to_card(&card.id, &m.id)
+                        .with_context(|| {
+                            format!(
+                                "Failed to assign '{}' to card '{}' ({})",
+                                m.username, card.name, card.id
+                            )
+                        })?;
+                    assigned.push(m.username.clone());
+                }
+
+                // Checklists can only be added once the card exists
+                for spec in &checklists {
+                    let created =
+                        client
+                            .create_checklist(&card.id, &spec.name)
+                            .with_context(|| {
+                                format!(
+                                    "Failed to create checklist '{}' on card '{}'",
+                                    spec.name, card.name
+                                )
+                            })?;
+                    for item in &spec.items {
+                        client.add_check_item(&created.id, item).with_context(|| {
+                            format!("Failed to add item '{}' to checklist '{}'", item, spec.name)
+                        })?;
+                    }
+                }
+
+                let mut details = Vec::new();
+                if let Some(ref source_name) = copied_from {
+                    details.push(format!("description copied from '{}'", source_name));
+                }
+                if let Some(date) = start {
+                    details.push(format!("start: {}", date));
+                }
+                if let Some(date) = due {
+                    details.push(format!("due: {}", date));
+                }
+                if !label.is_empty() {
+                    details.push(format!("labels: {}", label.join(", ")));
+                }
+                if !assigned.is_empty() {
+                    details.push(format!("assigned: {}", assigned.join(", ")));
+                }
+                if !checklists.is_empty() {
+                    let names: Vec<String> = checklists
+                        .iter()
+                        .map(|c| match c.items.len() {
+                            0 => c.name.clone(),
+                            n => format!("{} [{} items]", c.name, n),
+                        })
+                        .collect();
+                    details.push(format!("checklists: {}", names.join(", ")));
+                }
+                let details = if details.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", details.join("; "))
+                };
+
+                if quiet {
+                    print!("{}", card.id);
+                    io::stdout().flush().context("Failed to write card ID")?;
+                } else {
+                    println!(
+                        "Created card '{}' ({}) in list '{}'{}",
+                        card.name, card.id, list.name, details
+                    );
+                }
+            }
+            CardCommands::Update {
+                card_id,
+                name,
+                name_from_file,
+                name_from_stdin,
+                max_name_length,
+                description,
+                label,
+                clear_label,
+                clear_all_labels,
+                comment,
+                comment_from_file,
+                archive,
+                restore,
+                cover_color,
+                clear_cover,
+                position,
+                start,
+                clear_start,
+                assign_me,
+                unassign_me,
+                board,
+                quiet,
+            } => {
+                // Informational output, suppressed by --quiet
+                macro_rules! say {
+                    ($($arg:tt)*) => {
+                        if !quiet {
+                            println!($($arg)*);
+                        }
+                    };
+                }
+
+                if name.is_none()
+                    && name_from_file.is_none()
+                    && !name_from_stdin
+                    && description.is_none()
+                    && label.is_empty()
+                    && clear_label.is_empty()
+                    && !clear_all_labels
+                    && comment.is_empty()
+                    && comment_from_file.is_none()
+                    && !archive
+                    && !restore
+                    && cover_color.is_none()
+                    && !clear_cover
+                    && position.is_none()
+                    && start.is_none()
+                    && !clear_start
+                    && !assign_me
+                    && !unassign_me
+                {
+                    return Err(TrelloError::User(
+                        "at least one update flag must be provided".to_string(),
+                    )
+                    .into());
+                }
+
+                if archive && restore {
+                    return Err(TrelloError::User(
+                        "--archive and --restore are mutually exclusive".to_string(),
+                    )
+                    .into());
+                }
+
+                if cover_color.is_some() && clear_cover {
+                    return Err(TrelloError::User(
+                        "--cover-color and --clear-cover are mutually exclusive".to_string(),
+                    )
+                    .into());
+                }
+
+                if start.is_some() && clear_start {
+                    return Err(TrelloError::User(
+                        "--start and --clear-start are mutually exclusive".to_string(),
+                    )
+                    .into());
+                }
+
+                let name = read_card_name(
+                    name,
+                    name_from_file.as_deref(),
+                    name_from_stdin,
+                    io::stdin().lock(),
+                )?;
+                if let Some(ref name) = name {
+                    validate_card_name(name, max_name_length)?;
+                }
+                let description = description
+                    .map(|d| read_value_from_source(&d, io::stdin()))
+                    .transpose()?;
+                let comment = match comment_from_file {
+                    Some(ref path) => vec![read_file_value(path)?],
+                    None => comment,
+                };
+
+                let card_id = client
+                    .resolve_card(&card_id, board.as_deref())
+                    .with_context(|| format!("Failed to resolve card '{}'", card_id))?;
+
+                let needs_card = !label.is_empty()
+                    || !clear_label.is_empty()
+                    || clear_all_labels
+                    || !comment.is_empty()
+                    || archive
+                    || restore
+                    || assign_me
+                    || unassign_me;
+
+                if let Some(ref name) = name {
+                    client
+                        .update_card_name(&card_id, name)
+                        .with_context(|| format!("Failed to rename card '{}'", card_id))?;
+                    say!("Renamed card {} to '{}'", card_id, name);
+                }
+
+                // Update description
+                let desc_card_name = if let Some(ref desc) = description {
+                    let card = client
+                        .update_card_description(&card_id, desc)
+                        .with_context(|| {
+                            format!("Failed to update description of card '{}'", card_id)
+                        })?;
+                    Some(card.name)
+                } else {
+                    None
+                };
+
+                // Fetch card once for label/comment/archive operations
+                let card = if needs_card {
+                    Some(
+                        client
+                            .get_card(&card_id)
+                            .with_context(|| format!("Failed to fetch card '{}'", card_id))?,
+                    )
+                } else {
+                    None
+                };
+                let card_name = card
+                    .as_ref()
+                    .map(|c| c.name.clone())
+                    .or(desc_card_name)
+                    .or(name)
+                    .unwrap_or_else(|| card_id.clone());
+
+                if description.is_some() {
+                    say!("Updated description of card '{}'", card_name);
+                }
+
+                // Reposition within the current list (separate PUT, since numeric
+                // positions are computed from the neighbouring cards)
+                if let Some(ref pos) = position {
+                    client
+                        .move_card(&card_id, pos)
+                        .with_context(|| format!("Failed to move card '{}'", card_id))?;
+                    say!("Moved card '{}' to position {}", card_name, pos);
+                }
+
+                // Apply/remove labels
+                if !label.is_empty() || !clear_label.is_empty() {
+                    let card = card.as_ref().unwrap();
+                    let board_labels =
+                        client.get_board_labels(&card.id_board).with_context(|| {
+                            format!("Failed to fetch labels for card '{}'", card_id)
+                        })?;
+
+                    for label_name in &label {
+                        client
+                            .apply_label_by_name(card, &board_labels, label_name)
+                            .with_context(|| {
+                                format!(
+                                    "Failed to apply label '{}' to card '{}'",
+                                    label_name, card.name
+                                )
+                            })?;
+                        say!("Applied label '{}' to card '{}'", label_name, card.name);
+                    }
+
+                    for label_name in &clear_label {
+                        client
+                            .remove_label_by_name(card, &board_labels, label_name)
+                            .with_context(|| {
+                                format!(
+                                    "Failed to remove label '{}' from card '{}'",
+                                    label_name, card.name
+                                )
+                            })?;
+                        say!("Removed label '{}' from card '{}'", label_name, card.name);
+                    }
+                }
+
+                // Trello has no bulk endpoint, so remove the labels one at a time
+                if clear_all_labels {
+                    let card = card.as_ref().unwrap();
+                    for label_id in &card.id_labels {
+                        client
+                            .remove_label_from_card(&card.id, label_id)
+                            .with_context(|| {
+                                format!("Failed to remove labels from card '{}'", card.name)
+                            })?;
+                    }
+                    say!(
+                        "Cleared {} labels from card '{}'",
+                        card.id_labels.len(),
+                        card.name
+                    );
+                }
+
+                // Add comments
+                let mut comments_added = Vec::new();
+                for text in &comment {
+                    let trimmed = text.trim();
+                    if !trimmed.is_empty() {
+                        client
+                            .add_comment_to_card(&card_id, trimmed)
+                            .with_context(|| {
+                                format!("Failed to add comment to card '{}'", card_id)
+                            })?;
+                        comments_added.push(trimmed);
+                    }
+                }
+                match comments_added.as_slice() {
+                    [] => {}
+                    [text] => say!(
+                        "Added comment to card '{}' (\"{}\")",
+                        card_name,
+                        comment_preview(text)
+                    ),
+                    all => say!("Added {} comments to card '{}'", all.len(), card_name),
+                }
+
+                // Set or clear cover
+                if let Some(ref color) = cover_color {
+                    client
+                        .set_card_cover(&card_id, Some(color))
+                        .with_context(|| format!("Failed to set cover of card '{}'", card_id))?;
+                    say!("Set cover of card '{}' to {}", card_name, color);
+                } else if clear_cover {
+                    client
+                        .set_card_cover(&card_id, None)
+                        .with_context(|| format!("Failed to clear cover of card '{}'", card_id))?;
+                    say!("Cleared cover of card '{}'", card_name);
+                }
+
+                // Set or clear start date
+                if let Some(date) = start {
+                    client
+                        .update_card_start(&card_id, Some(&local_date_to_utc(date)))
+                        .with_context(|| {
+                            format!("Failed to set start date of card '{}'", card_id)
+                        })?;
+                    say!("Set start date of card '{}' to {}", card_name, date);
+                } else if clear_start {
+                    client.update_card_start(&card_id, None).with_context(|| {
+                        format!("Failed to clear start date of card '{}'", card_id)
+                    })?;
+                    say!("Cleared start date of card '{}'", card_name);
+                }
+
+                if assign_me || unassign_me {
+                    let card = card.as_ref().unwrap();
+                    let me = client
+                        .get_current_member()
+                        .context("Failed to fetch current member")?;
+                    let assigned = card.id_members.contains(&me.id);
+                    if assign_me && assigned {
+                        say!("You are already assigned to card '{}'", card_name);
+                    } else if assign_me {
+                        client
+                            .assign_member_to_card(&card.id, &me.id)
+                            .with_context(|| format!("Failed to assign card '{}'", card_id))?;
+                        say!("Assigned yourself to card '{}'", card_name);
+                    } else if !assigned {
+                        say!("You are not assigned to card '{}'", card_name);
+                    } else {
+                        client
+                            .remove_member_from_card(&card.id, &me.id)
+                            .with_context(|| format!("Failed to unassign card '{}'", card_id))?;
+                        say!("Unassigned yourself from card '{}'", card_name);
+                    }
+                }
+
+                // Archive or restore
+                if archive {
+                    let card = card.as_ref().unwrap();
+                    client
+                        .archive_card(card)
+                        .with_context(|| format!("Failed to archive card '{}'", card_id))?;
+                    say!("Archived card '{}'", card_name);
+                } else if restore {
+                    let card = card.as_ref().unwrap();
+                    client
+                        .restore_card(card)
+                        .with_context(|| format!("Failed to restore card '{}'", card_id))?;
+                    say!("Restored card '{}'", card_name);
+                }
+            }
+            CardCommands::Move { card_id, position } => {
+                let card = client.move_card(&card_id, &position)?;
+                println!("Moved card '{}' to position {}", card.name, position);
+            }
+            CardCommands::Delete { card_id } => {
+                let card = client
+                    .get_card(&card_id)
+                    .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
+                client
+                    .delete_card(&card_id)
+                    .with_context(|| format!("Failed to delete card '{}'", card_id))?;
+                println!("Deleted card '{}' ({})", card.name, card.id);
+            }
+            CardCommands::Vote { card_id, remove } => {
+                let card_id = client
+                    .resolve_card(&card_id, None)
+                    .with_context(|| format!("Failed to resolve card '{}'", card_id))?;
+                let card = client
+                    .get_card(&card_id)
+                    .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
+
+                if remove {
+                    let member = client
+                        .get_current_member()
+                        .context("Failed to fetch current member")?;
+                    client
+                        .remove_vote_from_card(&card.id, &member.id)
+                        .with_context(|| {
+                            format!("Failed to remove vote from card '{}'", card.name)
+                        })?;
+                    println!("Removed vote from card '{}'", card.name);
+                } else {
+                    client
+                        .vote_on_card(&card.id)
+                        .with_context(|| format!("Failed to vote on card '{}'", card.name))?;
+                    println!("Voted on card '{}'", card.name);
+                }
+            }
+            CardCommands::CommentReply {
+                card_id,
+                action_id,
+                text,
+            } => {
+                let card_id = client
+                    .resolve_card(&card_id, None)
+                    .with_context(|| format!("Failed to resolve card '{}'", card_id))?;
+                let card = client
+                    .get_card(&card_id)
+                    .with_context(|| format!("Failed to fetch card '{}'", card_id))?;
+                let action = client
+                    .get_action(&action_id)
+                    .with_context(|| format!("Failed to fetch comment '{}'", action_id))?;
+                let body = format_reply(&action, &text)?;
+
+                client
+                    .add_comment_to_card(&card.id, &body)
+                    .with_context(|| format!("Failed to add comment to card '{}'", card.name))?;
+                let author = action
+                    .member_creator
+                    .full_name
+                    .unwrap_or(action.member_creator.username);
+                println!("Replied to {}'s comment on card '{}'", author, card.name);
+            }
+            CardCommands::Find {
+                pattern,
+                board,
+                list,
+                json,
+                limit,
+                offset,
+                quiet,
+                quiet_on_empty,
+                interactive,
+                exact,
+                case_sensitive,
+                multiline,
+                whole_word,
+                board_id,
+                label,
+                has_label,
+                no_labels,
+                active_within_days,
+                show_url,
+                show_description,
+                limit_per_board,
+                group_by_list,
+                since_id,
+                print_cursor,
+                with_checklist_item,
+                max_cards_to_check,
+                output_file,
+                output_template_file,
+                append,
+                has_description,
+                no_description,
+                ndjson,
+                ndjson_with_meta,
+                include_closed_lists,
+                assigned_to_me,
+                not_assigned,
+                has_checklist,
+                no_checklist,
+                checklist_complete,
+                board_filter,
+                all_boards,
+                organization,
+                max_boards,
+                confirm_large,
+                not_in_list,
+                include_org_boards,
+                count,
+                json_schema: _,
+            } => {
+                let matcher = NameMatcher::new(
+                    &pattern,
+                    exact,
+                    RegexOptions {
+                        case_sensitive,
+                        multiline,
+                        whole_word,
+                    },
+                )?;
+                // Parse the template up front so mistakes are reported before searching
+                let output_template = match output_template_file {
+                    Some(ref path) => {
+                        let text = read_file_value(path)?;
+                        Some(TemplateEngine::parse(&text).with_context(|| {
+                            format!("Failed to load template '{}'", path.display())
+                        })?)
+                    }
+                    None => None,
+                };
+                let card_filter = CardFilter {
+                    labels: LabelFilter {
+                        all: label,
+                        any: has_label,
+                        none: no_labels,
+                    },
+                    has_description: if has_description {
+                        Some(true)
+                    } else if no_description {
+                        Some(false)
+                    } else {
+                        None
+                    },
+                    include_closed_lists,
+                    assignee: if assigned_to_me {
+                        // Looked up once, before searching any boards
+                        let me = client
+                            .get_current_member()
+                            .context("Failed to fetch current member")?;
+                        Some(AssigneeFilter::Member(me.id))
+                    } else if not_assigned {
+                        Some(AssigneeFilter::Unassigned)
+                    } else {
+                        None
+                    },
+                    checklist: if checklist_complete {
+                        Some(ChecklistFilter::Complete)
+                    } else if has_checklist {
+                        Some(ChecklistFilter::Present)
+                    } else if no_checklist {
+                        Some(ChecklistFilter::Absent)
+                    } else {
+                        None
+                    },
+                };
+
+                let organization_id = organization
+                    .as_deref()
+                    .map(|org| {
+                        client
+                            .resolve_organization_id(org)
+                            .with_context(|| format!("Failed to resolve workspace '{}'", org))
+                    })
+                    .transpose()?;
+                let scope = BoardScope {
+                    filter: if all_boards { "all" } else { &board_filter },
+                    organization_id: organization_id.as_deref(),
+                    include_org_boards,
+                };
+                let mut boards = resolve_boards(&client, &board, board_id.as_deref(), &scope)?;
+                if let Some(days) = active_within_days {
+                    let (active, skipped) = filter_active_boards(boards, days, Utc::now());
+                    if skipped > 0 {
+                        eprintln!("Skipping {} inactive boards", skipped);
+                    }
+                    boards = active;
+                }
+                if boards.is_empty() {
+                    let message = if board.is_empty() {
+                        "No boards found".to_string()
+                    } else {
+                        format!("No boards matching '{}' found", board.join("', '"))
+                    };
+                    return Ok(no_matches(&message, quiet_on_empty));
+                }
+                if !confirm_large {
+                    check_board_limit(boards.len(), max_boards)?;
+                }
+                eprintln!(
+                    "Searching {} board{}...",
+                    boards.len(),
+                    if boards.len() == 1 { "" } else { "s" }
+                );
+
+                // Take the cursor before searching so changes made mid-run are picked up next time
+                let cursor = if print_cursor {
+                    let mut latest: Option<String> = None;
+                    for b in &boards {
+                        let id = client.get_board_latest_action_id(&b.id).with_context(|| {
+                            format!("Failed to fetch latest action for board '{}'", b.name)
+                        })?;
+                        latest = latest.max(id);
+                    }
+                    latest
+                } else {
+                    None
+                };
+
+                let mut results = find_matching_cards(
+                    &client,
+                    &matcher,
+                    &card_filter,
+                    &boards,
+                    list.as_deref(),
+                    "open",
+                    since_id.as_deref(),
+                )?;
+                if let Some(ref cursor) = cursor {
+                    eprintln!("Next cursor: --since-id {}", cursor);
+                }
+                if !not_in_list.is_empty() {
+                    results = exclude_lists(results, &not_in_list);
+                }
+                if let Some(n) = limit_per_board {
+                    let (kept, capped) = cap_per_board(results, n);
+                    for board_name in &capped {
+                        eprintln!("[Showing first {} results from board '{}']", n, board_name);
+                    }
+                    results = kept;
+                }
+                if let Some(ref item_pattern) = with_checklist_item {
+                    let item_regex = build_name_regex(item_pattern)?;
+                    if results.len() > max_cards_to_check {
+                        eprintln!(
+                            "Checking checklists on the first {} of {} cards; raise --max-cards-to-check to check more",
+                            max_cards_to_check,
+                            results.len()
+                        );
+                        results.truncate(max_cards_to_check);
+                    } else {
+                        eprintln!(
+                            "Checking checklists on {} cards (one request per card)",
+                            results.len()
+                        );
+                    }
+
+                    let mut kept = Vec::new();
+                    for r in results {
+                        let checklists =
+                            client.get_card_checklists_brief(&r.id).with_context(|| {
+                                format!("Failed to fetch checklists for card '{}'", r.title)
+                            })?;
+                        if has_matching_check_item(&checklists, &item_regex) {
+                            kept.push(r);
+                        }
+                    }
+                    results = kept;
+                }
+
+                // Zero is a count like any other, so it isn't reported as "no matches"
+                if count {
+                    if json {
+                        println!("{}", serde_json::json!({ "count": results.len() }));
+                    } else {
+                        println!("{}", results.len());
+                    }
+                    return Ok(Status::Success);
+                }
+
+                if results.is_empty() {
+                    return Ok(no_matches("No cards found", quiet_on_empty));
+                }
+
+                if interactive {
+                    let display_strs: Vec<String> = results.iter().map(picker_label).collect();
+                    let selection = dialoguer::FuzzySelect::new()
+                        .with_prompt("Select a card")
+                        .items(&display_strs)
+                        .default(0)
+                        .interact_opt()
+                        .context("Failed to read selection")?;
+                    return match selection {
+                        Some(index) => {
+                            println!("{}", results[index].id);
+                            Ok(Status::Success)
+                        }
+                        None => Ok(Status::NoMatches),
+                    };
+                }
+
+                let total = results.len();
+                let mut results = paginate(results, offset, limit);
+                if !show_description {
+                    for r in &mut results {
+                        r.description = None;
+                    }
+                }
+
+                let mut out = output_writer(output_file.as_deref(), append)?;
+                if json {
+                    writeln!(
+                        out,
+                        "{}",
+                        card_results_json(&results, total, offset, limit)?
+                    )?;
+                } else if ndjson || ndjson_with_meta {
+                    let meta = ndjson_with_meta.then(|| NdjsonMeta {
+                        total: results.len(),
+                        query: &pattern,
+                        boards_searched: boards.len(),
+                    });
+                    write_ndjson(&results, meta, &mut out)?;
+                } else if group_by_list {
+                    print_grouped(&results, &mut out)?;
+                } else if let Some(ref template) = output_template {
+                    write!(out, "{}", template.render(&results))?;
+                } else {
+                    let mut header = String::from("ID\tBoard\tList\tTitle");
+                    if show_url {
+                        header.push_str("\tURL");
+                    }
+                    if show_description {
+                        header.push_str("\tDescription");
+                    }
+                    writeln!(out, "{}", header)?;
+                    for r in &results {
+                        let mut line = format!(
+                            "{}\t{}\t{}\t{}",
+                            r.id,
+                            sanitize_field(&r.board),
+                            list_label(r),
+                            sanitize_field(&r.title)
+                        );
+                        if show_url {
+                            line.push('\t');
+                            line.push_str(r.url.as_deref().unwrap_or_default());
+                        }
+                        if let Some(ref desc) = r.description {
+                            line.push('\t');
+                            line.push_str(&description_preview(desc));
+                        }
+                        writeln!(out, "{}", line)?;
+                    }
+                }
+                out.flush().context("Failed to write results")?;
+                if let Some(ref path) = output_file {
+                    println!("Wrote {} results to {}", results.len(), path.display());
+                }
+
+                if (limit > 0 || offset > 0) && !quiet {
+                    eprintln!("{}", pagination_footer(offset, results.len(), total));
+                }
+            }
+            CardCommands::BulkArchive {
+                pattern,
+                board,
+                list,
+                dry_run,
+                force,
+            } => {
+                let matcher = NameMatcher::new(&pattern, false, RegexOptions::default())?;
+                let boards =
+                    resolve_boards(&client, board.as_slice(), None, &BoardScope::default())?;
+                let matches = find_matching_cards(
+                    &client,
+                    &matcher,
+                    &CardFilter::default(),
+                    &boards,
+                    list.as_deref(),
+                    "all",
+                    None,
+                )?;
+                if matches.is_empty() {
+                    return Ok(no_matches("No cards found", false));
+                }
+
+                let (already_archived, to_archive): (Vec<_>, Vec<_>) =
+                    matches.into_iter().partition(|c| c.closed);
+
+                if dry_run || !force {
+                    for c in &to_archive {
+                        println!(
+                            "{} ({}/{})",
+                            sanitize_field(&c.title),
+                            sanitize_field(&c.board),
+                            sanitize_field(&c.list)
+                        );
+                    }
+                }
+
+                if dry_run {
+                    println!(
+                        "Would archive {} cards ({} already archived, skipped)",
+                        to_archive.len(),
+                        already_archived.len()
+                    );
+                    return Ok(Status::Success);
+                }
+
+                if !to_archive.is_empty()
+                    && !force
+                    && !confirm(&format!("Archive {} cards? [y/N] ", to_archive.len()))?
+                {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                for c in &to_archive {
+                    client
+                        .archive_card_by_id(&c.id)
+                        .with_context(|| format!("Failed to archive card '{}'", c.title))?;
+                }
+                println!(
+                    "Archived {} cards ({} already archived, skipped)",
+                    to_archive.len(),
+                    already_archived.len()
+                );
+            }
+            CardCommands::BulkMove {
+                pattern,
+                to_list,
+                to_board,
+                board,
+                list,
+                position,
+                dry_run,
+                force,
+                quiet,
+            } => {
+                let dest_id = client
+                    .resolve_list(&to_list, to_board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", to_list))?;
+                let dest = client
+                    .get_list(&dest_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", dest_id))?;
+
+                let matcher = NameMatcher::new(&pattern, false, RegexOptions::default())?;
+                let boards =
+                    resolve_boards(&client, board.as_slice(), None, &BoardScope::default())?;
+                let matches = find_matching_cards(
+                    &client,
+                    &matcher,
+                    &CardFilter::default(),
+                    &boards,
+                    list.as_deref(),
+                    "open",
+                    None,
+                )?;
+                if matches.is_empty() {
+                    return Ok(no_matches("No cards found", false));
+                }
+
+                let (already_there, mut to_move): (Vec<_>, Vec<_>) =
+                    matches.into_iter().partition(|c| c.list_id == dest.id);
+
+                if dry_run || !force {
+                    for c in &to_move {
+                        println!(
+                            "{} ({}/{})",
+                            sanitize_field(&c.title),
+                            sanitize_field(&c.board),
+                            sanitize_field(&c.list)
+                        );
+                    }
+                }
+
+                if dry_run {
+                    println!(
+                        "Would move {} cards to '{}' ({} already in list, skipped)",
+                        to_move.len(),
+                        dest.name,
+                        already_there.len()
+                    );
+                    return Ok(Status::Success);
+                }
+
+                if !to_move.is_empty()
+                    && !force
+                    && !confirm(&format!(
+                        "Move {} cards to '{}'? [y/N] ",
+                        to_move.len(),
+                        dest.name
+                    ))?
+                {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                // Moving each card to the top in turn would reverse their order
+                if position == "top" {
+                    to_move.reverse();
+                }
+
+                for c in &to_move {
+                    client
+                        .move_card_to_list(&c.id, &dest, &position)
+                        .with_context(|| format!("Failed to move card '{}'", c.title))?;
+                    if !quiet {
+                        println!("Moved '{}' to '{}'", c.title, dest.name);
+                    }
+                }
+                println!(
+                    "Moved {} cards to '{}' ({} already in list, skipped)",
+                    to_move.len(),
+                    dest.name,
+                    already_there.len()
+                );
+            }
+            CardCommands::Show {
+                card_ids,
+                json,
+                compact,
+                comments: include_comments,
+                since,
+                last_n_comments,
+                highlight_comments,
+                render_markdown,
+                raw_description,
+                output_format,
+                board,
+                show_position,
+                board_context,
+                webhooks,
+                field,
+                qr,
+                json_schema: _,
+            } => {
+                let fields = known_show_fields(&field);
+                let highlight_comments = highlight_comments
+                    .as_deref()
+                    .map(build_name_regex)
+                    .transpose()?;
+                let opts = ShowOptions {
+                    board,
+                    compact,
+                    include_comments,
+                    since,
+                    last_n_comments,
+                    render_markdown,
+                    raw_description,
+                    show_position,
+                    board_context,
+                    webhooks,
+                    highlight_comments,
+                };
+                // A single card is shown exactly as before: a JSON object rather than an
+                // array, no divider, and errors are returned rather than reported
+                let multiple = card_ids.len() > 1;
+                let mut json_results = Vec::new();
+                let mut shown = 0;
+                let mut failures = Vec::new();
+
+                for card_id in &card_ids {
+                    let card = match fetch_shown_card(&client, card_id, &opts) {
+                        Ok(card) => card,
+                        Err(e) if multiple => {
+                            eprintln!("Error: {e:#}");
+                            failures.push(e);
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    match card {
+                        ShownCard::Compact(result) => json_results.push(
+                            serde_json::to_value(&result).context("Failed to serialize result")?,
+                        ),
+                        ShownCard::Full { result, .. } if !field.is_empty() => {
+                            let selected = select_fields(&result, &fields)?;
+                            if json {
+                                json_results.push(serde_json::Value::Object(
+                                    selected.into_iter().collect(),
+                                ));
+                            } else {
+                                if shown > 0 {
+                                    println!("---");
+                                }
+                                for (name, value) in &selected {
+                                    println!("{}: {}", name, field_text(value));
+                                }
+                            }
+                        }
+                        ShownCard::Full { result, list_cards } => {
+                            if json {
+                                json_results.push(
+                                    serde_json::to_value(&result)
+                                        .context("Failed to serialize result")?,
+                                );
+                            } else if let Some(ref template) = output_format {
+                                println!("{}", render_card_template(template, &result));
+                            } else {
+                                if shown > 0 {
+                                    println!("---");
+                                }
+                                print_card_details(&result, &list_cards, &opts);
+                                if qr {
+                                    match result.short_url.as_deref() {
+                                        Some(short_url) => {
+                                            print!("{}", render_qr_to_string(short_url)?)
+                                        }
+                                        None => eprintln!(
+                                            "Warning: card '{}' has no short URL; skipping QR code",
+                                            result.name
+                                        ),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    shown += 1;
+                }
+
+                if json {
+                    let output = if multiple {
+                        serde_json::to_string(&json_results)
+                    } else {
+                        serde_json::to_string(&json_results[0])
+                    };
+                    println!("{}", output.context("Failed to serialize result")?);
+                }
+                if !failures.is_empty() {
+                    return Err(show_failures_error(failures, card_ids.len()));
+                }
+            }
+        },
+        Commands::List { command } => match command {
+            ListCommands::Show { list, board, json } => {
+                let list_id = client
+                    .resolve_list(&list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", list))?;
+                let list = client
+                    .get_list(&list_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
+                let board = client
+                    .get_board(&list.id_board)
+                    .with_context(|| format!("Failed to fetch board for list '{}'", list_id))?;
+
+                if json {
+                    let result = serde_json::json!({
+                        "id": list.id,
+                        "name": list.name,
+                        "board": board.name,
+                        "position": list.pos,
+                    });
+                    println!(
+                        "{}",
+                        serde_json::to_string(&result).context("Failed to serialize result")?
+                    );
+                } else {
+                    println!("Name: {}", list.name);
+                    println!("ID: {}", list.id);
+                    println!("Board: {}", board.name);
+                    println!("Position: {}", list.pos);
+                }
+            }
+            ListCommands::Find {
+                pattern,
+                board,
+                json,
+            } => {
+                let regex = build_name_regex(&pattern)?;
+                let boards =
+                    resolve_boards(&client, board.as_slice(), None, &BoardScope::default())?;
+
+                let mut results: Vec<ListResult> = Vec::new();
+                for b in &boards {
+                    let lists = client
+                        .get_board_lists(&b.id)
+                        .with_context(|| format!("Failed to fetch lists for board '{}'", b.name))?;
+                    results.extend(lists.into_iter().filter(|l| regex.is_match(&l.name)).map(
+                        |l| ListResult {
+                            id: l.id,
+                            board: b.name.clone(),
+                            name: l.name,
+                        },
+                    ));
+                }
+
+                if results.is_empty() {
+                    return Ok(no_matches("No lists found", false));
+                }
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&results).context("Failed to serialize results")?
+                    );
+                } else {
+                    println!("ID\tBoard\tName");
+                    for r in &results {
+                        println!(
+                            "{}\t{}\t{}",
+                            r.id,
+                            sanitize_field(&r.board),
+                            sanitize_field(&r.name)
+                        );
+                    }
+                }
+            }
+            ListCommands::Cards {
+                list,
+                board,
+                json,
+                archived,
+                sort,
+                sort_desc,
+                nulls_first,
+            } => {
+                let list_id = client
+                    .resolve_list(&list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", list))?;
+                let list = client
+                    .get_list(&list_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
+                let board = client
+                    .get_board(&list.id_board)
+                    .with_context(|| format!("Failed to fetch board for list '{}'", list_id))?;
+                let board_labels = client.get_board_labels(&list.id_board).with_context(|| {
+                    format!("Failed to fetch labels for board '{}'", board.name)
+                })?;
+                let label_names: HashMap<String, String> = board_labels
+                    .into_iter()
+                    .map(|l| {
+                        let name = if l.name.is_empty() {
+                            l.color.unwrap_or_default()
+                        } else {
+                            l.name
+                        };
+                        (l.id, name)
+                    })
+                    .collect();
+
+                let filter = if archived { "all" } else { "open" };
+                let mut cards = client
+                    .get_list_cards_filtered(&list_id, filter)
+                    .with_context(|| format!("Failed to fetch cards for list '{}'", list.name))?;
+                sort_cards(&mut cards, sort, sort_desc, nulls_first);
+
+                let results: Vec<CardResult> = cards
+                    .into_iter()
+                    .map(|card| CardResult {
+                        id: card.id,
+                        board: board.name.clone(),
+                        list: list.name.clone(),
+                        title: card.name,
+                        board_id: card.id_board,
+                        list_id: card.id_list,
+                        closed: card.closed,
+                        list_closed: list.closed,
+                        pos: Some(card.pos),
+                        labels: Some(
+                            card.id_labels
+                                .iter()
+                                .filter_map(|id| label_names.get(id).cloned())
+                                .collect(),
+                        ),
+                        url: card.short_url,
+                        description: None,
+                    })
+                    .collect();
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&results).context("Failed to serialize results")?
+                    );
+                } else {
+                    println!("ID\tPosition\tTitle\tLabels");
+                    for r in &results {
+                        println!(
+                            "{}\t{}\t{}\t{}",
+                            r.id,
+                            r.pos.unwrap_or_default(),
+                            sanitize_field(&r.title),
+                            sanitize_field(&r.labels.as_deref().unwrap_or_default().join(", "))
+                        );
+                    }
+                }
+            }
+            ListCommands::Move { list_id, position } => {
                 let list = client.move_list(&list_id, &position)?;
                 println!("Moved list '{}' to position {}", list.name, position);
             }
-        },
-        Commands::Board { command } => match command {
-            BoardCommands::Show { board_id, json } => {
-                let board = client
-                    .get_board(&board_id)
-                    .with_context(|| format!("Failed to fetch board '{}'", board_id))?;
+            ListCommands::MoveCards {
+                src_list,
+                dst_list,
+                board,
+                filter_label,
+                dry_run,
+                force,
+            } => {
+                let src_id = client
+                    .resolve_list(&src_list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", src_list))?;
+                let dst_id = client
+                    .resolve_list(&dst_list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", dst_list))?;
+                let src = client
+                    .get_list(&src_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", src_id))?;
+                let dst = client
+                    .get_list(&dst_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", dst_id))?;
+
+                let label_id = match filter_label {
+                    Some(ref label_name) => {
+                        let labels = client.get_board_labels(&src.id_board).with_context(|| {
+                            format!("Failed to fetch labels for list '{}'", src.name)
+                        })?;
+                        let label = labels
+                            .into_iter()
+                            .find(|l| l.name.eq_ignore_ascii_case(label_name))
+                            .ok_or_else(|| {
+                                TrelloError::NotFound(format!(
+                                    "Label '{}' not found on board",
+                                    label_name
+                                ))
+                            })?;
+                        Some(label.id)
+                    }
+                    None => None,
+                };
+
+                let cards = client
+                    .get_list_cards(&src.id)
+                    .with_context(|| format!("Failed to fetch cards for list '{}'", src.name))?;
+                let to_move = plan_list_moves(cards, label_id.as_deref());
+
+                if to_move.is_empty() {
+                    println!("No cards to move from '{}'", src.name);
+                    return Ok(Status::Success);
+                }
+
+                if dry_run {
+                    for card in &to_move {
+                        println!("Would move '{}' to '{}'", card.name, dst.name);
+                    }
+                    println!(
+                        "Would move {} cards from '{}' to '{}'",
+                        to_move.len(),
+                        src.name,
+                        dst.name
+                    );
+                    return Ok(Status::Success);
+                }
+
+                if !force
+                    && !confirm(&format!(
+                        "Move {} cards from '{}' to '{}'? [y/N] ",
+                        to_move.len(),
+                        src.name,
+                        dst.name
+                    ))?
+                {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                // Appending each card to the bottom in source order preserves their relative order
+                for card in &to_move {
+                    client
+                        .move_card_to_list(&card.id, &dst, "bottom")
+                        .with_context(|| format!("Failed to move card '{}'", card.name))?;
+                    println!("Moved '{}' to '{}'", card.name, dst.name);
+                }
+                println!(
+                    "Moved {} cards from '{}' to '{}'",
+                    to_move.len(),
+                    src.name,
+                    dst.name
+                );
+            }
+            ListCommands::CreateFromTemplate {
+                template_board,
+                dest_board,
+                include_cards,
+            } => {
+                let template = client
+                    .resolve_board(&template_board)
+                    .with_context(|| format!("Failed to resolve board '{}'", template_board))?;
+                let dest = client
+                    .resolve_board(&dest_board)
+                    .with_context(|| format!("Failed to resolve board '{}'", dest_board))?;
+                let lists =
+                    sort_lists_by_pos(client.get_board_lists(&template.id).with_context(|| {
+                        format!("Failed to fetch lists for board '{}'", template.name)
+                    })?);
+
+                let mut cards_copied = 0;
+                let count = for_each_list(&lists, "Created", |l| {
+                    let created = client.create_list(&dest.id, &l.name).with_context(|| {
+                        format!("Failed to create list '{}' on '{}'", l.name, dest.name)
+                    })?;
+                    if include_cards {
+                        let mut cards = client.get_list_cards(&l.id).with_context(|| {
+                            format!("Failed to fetch cards for list '{}'", l.name)
+                        })?;
+                        cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+                        for card in &cards {
+                            client
+                                .copy_card(&card.id, &created.id)
+                                .with_context(|| format!("Failed to copy card '{}'", card.name))?;
+                        }
+                        cards_copied += cards.len();
+                    }
+                    Ok(())
+                })?;
+
+                if include_cards {
+                    println!(
+                        "Copied {} lists and {} cards from '{}' to '{}'",
+                        count, cards_copied, template.name, dest.name
+                    );
+                } else {
+                    println!(
+                        "Copied {} lists from '{}' to '{}'",
+                        count, template.name, dest.name
+                    );
+                }
+            }
+            ListCommands::Create {
+                board,
+                name,
+                position,
+                after_list,
+                before_list,
+                copy_from,
+            } => {
+                let board = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?;
+
+                let reference = after_list
+                    .as_deref()
+                    .map(|list| (list, Some(true)))
+                    .or(before_list.as_deref().map(|list| (list, Some(false))))
+                    .or(copy_from.as_deref().map(|list| (list, None)));
+                let pos = match reference {
+                    Some((reference, after)) => {
+                        let mut lists = client.get_board_lists(&board.id).with_context(|| {
+                            format!("Failed to fetch lists for board '{}'", board.name)
+                        })?;
+                        lists.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+                        let index = find_reference_list(&lists, reference)?;
+                        match after {
+                            Some(after) => {
+                                let positions: Vec<f64> = lists.iter().map(|l| l.pos).collect();
+                                position_next_to(&positions, index, after)
+                            }
+                            None => lists[index].pos.to_string(),
+                        }
+                    }
+                    None => position.unwrap_or_else(|| "bottom".to_string()),
+                };
+
+                let list = client
+                    .create_list_at(&board.id, &name, &pos)
+                    .with_context(|| {
+                        format!("Failed to create list '{}' on '{}'", name, board.name)
+                    })?;
+                println!(
+                    "Created list '{}' on '{}' ({})",
+                    list.name, board.name, list.id
+                );
+            }
+            ListCommands::Copy {
+                src_list,
+                dst_board,
+                board,
+                new_name,
+                include_cards,
+            } => {
+                let list_id = client
+                    .resolve_list_including_closed(&src_list, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", src_list))?;
+                let list = client
+                    .get_list(&list_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
+                let dest = client
+                    .resolve_board(&dst_board)
+                    .with_context(|| format!("Failed to resolve board '{}'", dst_board))?;
+
+                let name = new_name.unwrap_or_else(|| list.name.clone());
+                let created = client.create_list(&dest.id, &name).with_context(|| {
+                    format!("Failed to create list '{}' on '{}'", name, dest.name)
+                })?;
+                println!("Created list '{}' on '{}'", created.name, dest.name);
+
+                if include_cards {
+                    let mut cards = client.get_list_cards(&list.id).with_context(|| {
+                        format!("Failed to fetch cards for list '{}'", list.name)
+                    })?;
+                    cards.sort_by(|a, b| a.pos.partial_cmp(&b.pos).unwrap());
+                    for (i, card) in cards.iter().enumerate() {
+                        client
+                            .copy_card(&card.id, &created.id)
+                            .with_context(|| format!("Failed to copy card '{}'", card.name))?;
+                        println!(
+                            "[{}/{}] Copied '{}'",
+                            i + 1,
+                            cards.len(),
+                            sanitize_field(&card.name)
+                        );
+                    }
+                    println!(
+                        "Copied list '{}' and {} cards to '{}'",
+                        list.name,
+                        cards.len(),
+                        dest.name
+                    );
+                } else {
+                    println!("Copied list '{}' to '{}'", list.name, dest.name);
+                }
+            }
+            ListCommands::DeleteArchivedCards {
+                list_id,
+                board,
+                dry_run,
+                force,
+            } => {
+                let list_id = client
+                    .resolve_list(&list_id, board.as_deref())
+                    .with_context(|| format!("Failed to resolve list '{}'", list_id))?;
+                let list = client
+                    .get_list(&list_id)
+                    .with_context(|| format!("Failed to fetch list '{}'", list_id))?;
+                let cards = client
+                    .get_list_cards_filtered(&list.id, "closed")
+                    .with_context(|| {
+                        format!("Failed to fetch archived cards for list '{}'", list.name)
+                    })?;
+
+                if cards.is_empty() {
+                    println!("No archived cards in list '{}'", list.name);
+                    return Ok(Status::Success);
+                }
+
+                if dry_run {
+                    for card in &cards {
+                        println!("Would delete '{}' ({})", card.name, card.id);
+                    }
+                    println!(
+                        "Would permanently delete {} archived cards from list '{}'",
+                        cards.len(),
+                        list.name
+                    );
+                    return Ok(Status::Success);
+                }
+
+                if !force
+                    && !confirm(&format!(
+                        "Permanently delete {} archived cards from '{}'? This cannot be undone. [y/N] ",
+                        cards.len(),
+                        list.name
+                    ))?
+                {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                for card in &cards {
+                    client
+                        .delete_card(&card.id)
+                        .with_context(|| format!("Failed to delete card '{}'", card.name))?;
+                }
+                println!(
+                    "Permanently deleted {} archived cards from list '{}'",
+                    cards.len(),
+                    list.name
+                );
+            }
+        },
+        Commands::Board { command } => match command {
+            BoardCommands::Show { board, json, lists } => {
+                let board_id = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?
+                    .id;
+                let details = client
+                    .get_board_with_details(&board_id)
+                    .with_context(|| format!("Failed to fetch board '{}'", board_id))?;
+                let detail = board_detail(details);
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&detail).context("Failed to serialize result")?
+                    );
+                } else {
+                    println!("Name: {}", detail.name);
+                    println!("ID: {}", detail.id);
+                    if let Some(ref url) = detail.short_url {
+                        println!("URL: {}", url);
+                    }
+                    if detail.closed {
+                        println!("Status: closed");
+                    }
+                    println!("Members: {}", detail.member_count);
+                    println!("Lists: {}", detail.lists.len());
+                    if lists {
+                        for l in &detail.lists {
+                            println!("  {} ({} cards)", sanitize_field(&l.name), l.card_count);
+                        }
+                    }
+                }
+            }
+            BoardCommands::List {
+                recent,
+                json,
+                url,
+                sort,
+                sort_desc,
+                with_card_counts,
+            } => {
+                let mut boards = client
+                    .get_member_boards()
+                    .context("Failed to fetch boards")?;
+                if let Some(n) = recent {
+                    boards = most_recent_boards(boards, n as usize);
+                }
+                // Sorting by card count needs the counts even if they aren't shown
+                let fetch_counts = with_card_counts || sort == Some(BoardSortField::CardCount);
+                let mut results = Vec::with_capacity(boards.len());
+                for b in boards {
+                    let card_count = if fetch_counts {
+                        let cards = client.get_board_cards(&b.id).with_context(|| {
+                            format!("Failed to fetch cards for board '{}'", b.name)
+                        })?;
+                        Some(cards.len() as u32)
+                    } else {
+                        None
+                    };
+                    results.push(BoardResult {
+                        id: b.id,
+                        name: b.name,
+                        date_last_activity: b.date_last_activity,
+                        short_url: b.short_url,
+                        card_count,
+                    });
+                }
+                if let Some(field) = sort {
+                    sort_boards(&mut results, field, sort_desc);
+                }
+                if !with_card_counts {
+                    for r in &mut results {
+                        r.card_count = None;
+                    }
+                }
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&results).context("Failed to serialize results")?
+                    );
+                } else if url {
+                    for r in &results {
+                        println!(
+                            "{}\t{}",
+                            sanitize_field(&r.name),
+                            r.short_url.as_deref().unwrap_or_default()
+                        );
+                    }
+                } else if with_card_counts {
+                    println!("ID\tName\tLast activity\tCards\tURL");
+                    for r in &results {
+                        println!(
+                            "{}\t{}\t{}\t{}\t{}",
+                            r.id,
+                            sanitize_field(&r.name),
+                            r.date_last_activity
+                                .as_deref()
+                                .map(format_comment_date)
+                                .unwrap_or_default(),
+                            r.card_count.unwrap_or_default(),
+                            r.short_url.as_deref().unwrap_or_default()
+                        );
+                    }
+                } else {
+                    println!("ID\tName\tLast activity\tURL");
+                    for r in &results {
+                        println!(
+                            "{}\t{}\t{}\t{}",
+                            r.id,
+                            sanitize_field(&r.name),
+                            r.date_last_activity
+                                .as_deref()
+                                .map(format_comment_date)
+                                .unwrap_or_default(),
+                            r.short_url.as_deref().unwrap_or_default()
+                        );
+                    }
+                }
+            }
+            BoardCommands::Find {
+                pattern,
+                json,
+                closed,
+            } => {
+                let regex = build_name_regex(&pattern)?;
+                let filter = if closed { "all" } else { "open" };
+                let boards = client
+                    .get_member_boards_filtered(filter, None)
+                    .context("Failed to fetch boards")?;
+                let results: Vec<BoardResult> = boards
+                    .into_iter()
+                    .filter(|b| regex.is_match(&b.name))
+                    .map(|b| BoardResult {
+                        id: b.id,
+                        name: b.name,
+                        date_last_activity: None,
+                        short_url: b.short_url,
+                        card_count: None,
+                    })
+                    .collect();
+
+                if results.is_empty() {
+                    return Ok(no_matches("No boards found", false));
+                }
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&results).context("Failed to serialize results")?
+                    );
+                } else {
+                    println!("ID\tName");
+                    for r in &results {
+                        println!("{}\t{}", r.id, sanitize_field(&r.name));
+                    }
+                }
+            }
+            BoardCommands::ArchiveAllLists { board, force } => {
+                let board = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?;
+                let lists = client
+                    .get_board_lists(&board.id)
+                    .with_context(|| format!("Failed to fetch lists for board '{}'", board.name))?;
+
+                if lists.is_empty() {
+                    println!("No open lists on board '{}'", board.name);
+                    return Ok(Status::Success);
+                }
+
+                if !force
+                    && !confirm(&format!(
+                        "Archive {} lists on board '{}'? [y/N] ",
+                        lists.len(),
+                        board.name
+                    ))?
+                {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                let count = for_each_list(&lists, "Archived", |l| {
+                    client
+                        .archive_list(&l.id)
+                        .with_context(|| format!("Failed to archive list '{}'", l.name))?;
+                    Ok(())
+                })?;
+                println!("Archived {} lists on board '{}'", count, board.name);
+            }
+            BoardCommands::RestoreAllLists { board } => {
+                let board = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?;
+                let lists = client.get_board_lists_closed(&board.id).with_context(|| {
+                    format!("Failed to fetch archived lists for board '{}'", board.name)
+                })?;
+
+                let count = for_each_list(&lists, "Restored", |l| {
+                    client
+                        .restore_list(&l.id)
+                        .with_context(|| format!("Failed to restore list '{}'", l.name))?;
+                    Ok(())
+                })?;
+                println!("Restored {} lists on board '{}'", count, board.name);
+            }
+            BoardCommands::Close { board, force } => {
+                let board = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?;
+
+                if !force && !confirm(&format!("Close board '{}'? [y/N] ", board.name))? {
+                    println!("Aborted");
+                    return Ok(Status::Success);
+                }
+
+                client
+                    .close_board(&board.id)
+                    .with_context(|| format!("Failed to close board '{}'", board.name))?;
+                println!("Closed board '{}'", board.name);
+            }
+            BoardCommands::Reopen { board } => {
+                let board = client
+                    .resolve_board_with_filter(&board, "closed")
+                    .with_context(|| format!("Failed to resolve closed board '{}'", board))?;
+                client
+                    .reopen_board(&board.id)
+                    .with_context(|| format!("Failed to reopen board '{}'", board.name))?;
+                println!("Reopened board '{}'", board.name);
+            }
+            BoardCommands::Activity {
+                board,
+                limit,
+                json,
+                filter,
+            } => {
+                let board = client
+                    .resolve_board(&board)
+                    .with_context(|| format!("Failed to resolve board '{}'", board))?;
+                let actions = client
+                    .get_board_actions(&board.id, limit as usize, filter.as_deref())
+                    .with_context(|| {
+                        format!("Failed to fetch activity for board '{}'", board.name)
+                    })?;
+
+                if json {
+                    println!(
+                        "{}",
+                        serde_json::to_string(&actions).context("Failed to serialize actions")?
+                    );
+                } else if actions.is_empty() {
+                    println!("No activity on board '{}'", board.name);
+                } else {
+                    for action in &actions {
+                        println!("{}", format_action_line(action));
+                    }
+                }
+            }
+            BoardCommands::Members { command } => match command {
+                BoardMemberCommands::List { board } => {
+                    let board = client
+                        .resolve_board(&board)
+                        .with_context(|| format!("Failed to resolve board '{}'", board))?;
+                    let members = client.get_board_members(&board.id).with_context(|| {
+                        format!("Failed to fetch members of board '{}'", board.name)
+                    })?;
+
+                    println!("Username\tName\tRole");
+                    for m in &members {
+                        println!(
+                            "{}\t{}\t{}",
+                            m.username,
+                            sanitize_field(m.full_name.as_deref().unwrap_or_default()),
+                            m.member_type
+                        );
+                    }
+                }
+                BoardMemberCommands::Invite { board, email, role } => {
+                    let board = client
+                        .resolve_board(&board)
+                        .with_context(|| format!("Failed to resolve board '{}'", board))?;
+                    let role = role.unwrap_or_else(|| "normal".to_string());
+                    client
+                        .invite_board_member(&board.id, &email, &role)
+                        .with_context(|| {
+                            format!("Failed to invite {} to board '{}'", email, board.name)
+                        })?;
+                    println!("Invited {} as {} to board '{}'", email, role, board.name);
+                }
+            },
+        },
+    }
+
+    Ok(Status::Success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::test_card;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parse_login_with_verify() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "login",
+            "--api-key",
+            "key",
+            "--api-token",
+            "token",
+            "--verify",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Login {
+                api_key,
+                api_token,
+                verify,
+                oauth,
+                ..
+            } => {
+                assert_eq!(api_key, Some("key".to_string()));
+                assert_eq!(api_token, Some("token".to_string()));
+                assert!(verify);
+                assert!(!oauth);
+            }
+            _ => panic!("Expected Login command"),
+        }
+    }
+
+    #[test]
+    fn parse_login_with_oauth() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "login",
+            "--oauth",
+            "--api-key",
+            "key",
+            "--api-secret",
+            "secret",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Login {
+                oauth, api_secret, ..
+            } => {
+                assert!(oauth);
+                assert_eq!(api_secret, Some("secret".to_string()));
+            }
+            _ => panic!("Expected Login command"),
+        }
+
+        let result = Cli::try_parse_from(["trello", "login", "--oauth", "--api-token", "token"]);
+        assert!(result.is_err());
+        let result = Cli::try_parse_from(["trello", "login", "--api-secret", "secret"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_timeout_flag() {
+        let cli = Cli::try_parse_from(["trello", "--timeout", "5", "whoami"]).unwrap();
+        assert_eq!(cli.timeout, Some(5));
+
+        let cli = Cli::try_parse_from(["trello", "whoami", "--timeout", "0"]).unwrap();
+        assert_eq!(cli.timeout, Some(0));
+
+        let cli = Cli::try_parse_from(["trello", "whoami"]).unwrap();
+        assert_eq!(cli.timeout, None);
+
+        assert!(Cli::try_parse_from(["trello", "--timeout", "301", "whoami"]).is_err());
+        assert!(Cli::try_parse_from(["trello", "--timeout", "-1", "whoami"]).is_err());
+    }
+
+    #[test]
+    fn parse_config_commands() {
+        let cli = Cli::try_parse_from(["trello", "config", "path"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                command: ConfigCommands::Path
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["trello", "config", "edit"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                command: ConfigCommands::Edit
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["trello", "config", "validate"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Config {
+                command: ConfigCommands::Validate
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_whoami_with_json() {
+        let cli = Cli::try_parse_from(["trello", "whoami", "--json"]).unwrap();
+        match cli.command {
+            Commands::Whoami { json } => assert!(json),
+            _ => panic!("Expected Whoami command"),
+        }
+    }
+
+    #[test]
+    fn test_whoami_result_serialization() {
+        let result = WhoamiResult {
+            id: "507f1f77bcf86cd799439011".to_string(),
+            username: "alice".to_string(),
+            full_name: None,
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed["id"], "507f1f77bcf86cd799439011");
+        assert_eq!(parsed["username"], "alice");
+        assert_eq!(parsed["full_name"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn parse_card_update_description() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "-d",
+            "New description",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    description,
+                    label,
+                    clear_label,
+                    comment,
+                    archive,
+                    restore,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(description, Some("New description".to_string()));
+                    assert!(label.is_empty());
+                    assert!(clear_label.is_empty());
+                    assert!(comment.is_empty());
+                    assert!(!archive);
+                    assert!(!restore);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_checklist_spec() {
+        assert_eq!(
+            parse_checklist_spec("Release: Tag build , Write notes").unwrap(),
+            ChecklistSpec {
+                name: "Release".to_string(),
+                items: vec!["Tag build".to_string(), "Write notes".to_string()],
+            }
+        );
+        assert!(parse_checklist_spec("Release").is_err());
+        assert!(parse_checklist_spec(":item").is_err());
+        assert!(parse_checklist_spec("Release:").is_err());
+        assert!(parse_checklist_spec("Release:a,,b").is_err());
+    }
+
+    #[test]
+    fn test_merge_checklists() {
+        let spec = |name: &str, items: &[&str]| ChecklistSpec {
+            name: name.to_string(),
+            items: items.iter().map(|i| i.to_string()).collect(),
+        };
+        let merged = merge_checklists(
+            vec!["QA".to_string(), "Release".to_string()],
+            vec![spec("Release", &["Tag"]), spec("Docs", &["README"])],
+        );
+        assert_eq!(
+            merged,
+            vec![
+                spec("QA", &[]),
+                spec("Release", &["Tag"]),
+                spec("Docs", &["README"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_card_create_dates() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "Todo",
+            "Ship it",
+            "--due",
+            "2024-03-08",
+            "--start",
+            "2024-03-01",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Create { due, start, .. },
+            } => {
+                assert_eq!(due, NaiveDate::from_ymd_opt(2024, 3, 8));
+                assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 1));
+            }
+            _ => panic!("Expected Card Create command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "Todo",
+            "Ship it",
+            "--due",
+            "03/08/2024",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_create_checklists() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "Todo",
+            "Ship it",
+            "--checklist",
+            "QA",
+            "--checklist-items",
+            "Release:Tag,Notes",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Create {
+                        checklist,
+                        checklist_items,
+                        ..
+                    },
+            } => {
+                assert_eq!(checklist, vec!["QA".to_string()]);
+                assert_eq!(checklist_items[0].name, "Release");
+                assert_eq!(checklist_items[0].items, vec!["Tag", "Notes"]);
+            }
+            _ => panic!("Expected Card Create command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "Todo",
+            "Ship it",
+            "--checklist-items",
+            "Release",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_comment_preview() {
+        assert_eq!(comment_preview("Deploy completed"), "Deploy completed");
+        assert_eq!(
+            comment_preview(
+                "Deploy completed\n\nAll 312 tests passed on staging; promoting build 4.2.1"
+            ),
+            "Deploy completed All 312 tests passed on staging;..."
+        );
+    }
+
+    #[test]
+    fn parse_card_update_comment_from_file() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "--comment-from-file",
+            "notes.txt",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Update {
+                        comment_from_file, ..
+                    },
+            } => assert_eq!(comment_from_file, Some(PathBuf::from("notes.txt"))),
+            _ => panic!("Expected Card Update command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "-c",
+            "hi",
+            "--comment-from-file",
+            "notes.txt",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_update_clear_all_labels() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "--clear-all-labels"])
+            .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Update {
+                        clear_all_labels, ..
+                    },
+            } => assert!(clear_all_labels),
+            _ => panic!("Expected Card Update command"),
+        }
+
+        for conflicting in ["--label", "--clear-label"] {
+            let result = Cli::try_parse_from([
+                "trello",
+                "card",
+                "update",
+                "abc123",
+                "--clear-all-labels",
+                conflicting,
+                "Bug",
+            ]);
+            assert!(result.is_err(), "{} should conflict", conflicting);
+        }
+    }
+
+    #[test]
+    fn parse_card_create_and_update_quiet() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "create", "list123", "Card", "-q"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Create { quiet, .. },
+            } => assert!(quiet),
+            _ => panic!("Expected Card Create command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "-a", "--quiet"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Update { quiet, archive, .. },
+            } => {
+                assert!(quiet);
+                assert!(archive);
+            }
+            _ => panic!("Expected Card Update command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_assign_me() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "--assign-me"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Update {
+                        assign_me,
+                        unassign_me,
+                        ..
+                    },
+            } => {
+                assert!(assign_me);
+                assert!(!unassign_me);
+            }
+            _ => panic!("Expected Card Update command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "--unassign-me"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Update { unassign_me, .. },
+            } => assert!(unassign_me),
+            _ => panic!("Expected Card Update command"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "update",
+                "abc123",
+                "--assign-me",
+                "--unassign-me"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_card_update_name() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "--name", "New name"])
+            .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Update {
+                        name,
+                        max_name_length,
+                        ..
+                    },
+            } => {
+                assert_eq!(name.as_deref(), Some("New name"));
+                assert_eq!(max_name_length, DEFAULT_MAX_NAME_LENGTH);
+            }
+            _ => panic!("Expected Card Update command"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "update",
+                "abc123",
+                "--name",
+                "x",
+                "--name-from-stdin"
+            ])
+            .is_err()
+        );
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "update",
+                "abc123",
+                "--name-from-file",
+                "name.txt",
+                "--name-from-stdin"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_card_name() {
+        assert!(validate_card_name("Fix login bug", 512).is_ok());
+
+        for empty in ["", "   \t"] {
+            let err = validate_card_name(empty, 512).unwrap_err();
+            assert_eq!(error::exit_code(&err), error::ExitCode::UserError);
+            assert!(err.to_string().contains("empty"), "got {}", err);
+        }
+
+        // Over --max-name-length is only a warning; over the API limit is an error
+        assert!(validate_card_name(&"a".repeat(600), 512).is_ok());
+        assert!(validate_card_name(&"é".repeat(TRELLO_MAX_NAME_LENGTH), 512).is_ok());
+        let err = validate_card_name(&"a".repeat(TRELLO_MAX_NAME_LENGTH + 1), 512).unwrap_err();
+        assert_eq!(error::exit_code(&err), error::ExitCode::UserError);
+        assert!(err.to_string().contains("16384"), "got {}", err);
+    }
+
+    #[test]
+    fn test_read_card_name() {
+        assert_eq!(
+            read_card_name(Some("Literal".to_string()), None, false, io::empty()).unwrap(),
+            Some("Literal".to_string())
+        );
+        assert_eq!(
+            read_card_name(None, None, false, io::empty()).unwrap(),
+            None
+        );
+        assert_eq!(
+            read_card_name(None, None, true, "  From stdin \nsecond line\n".as_bytes()).unwrap(),
+            Some("From stdin".to_string())
+        );
+        assert_eq!(
+            read_card_name(None, None, true, io::empty()).unwrap(),
+            Some(String::new())
+        );
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("name.txt");
+        fs::write(&path, "From file\r\nignored\n").unwrap();
+        assert_eq!(
+            read_card_name(None, Some(&path), false, io::empty()).unwrap(),
+            Some("From file".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_card_update_start() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "--start",
+            "2024-03-01",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    start, clear_start, ..
+                } => {
+                    assert_eq!(start, NaiveDate::from_ymd_opt(2024, 3, 1));
+                    assert!(!clear_start);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "--start",
+            "03/01/2024",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_local_date_to_utc_round_trips_to_local_midnight() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let utc = local_date_to_utc(date);
+        assert!(utc.ends_with('Z'), "got {}", utc);
+
+        let local = DateTime::parse_from_rfc3339(&utc)
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(local.date_naive(), date);
+        assert_eq!(local.time(), chrono::NaiveTime::MIN);
+    }
+
+    #[test]
+    fn parse_card_create_minimal() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "507f1f77bcf86cd799439011",
+            "Card name",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Create {
+                    list,
+                    name,
+                    description,
+                    position,
+                    board,
+                    ..
+                } => {
+                    assert_eq!(list, "507f1f77bcf86cd799439011");
+                    assert_eq!(name, Some("Card name".to_string()));
+                    assert_eq!(description, None);
+                    assert_eq!(position, None);
+                    assert_eq!(board, None);
+                }
+                _ => panic!("Expected Create command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_create_with_all_optional_flags() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "list123",
+            "Card name",
+            "-d",
+            "desc",
+            "-p",
+            "top",
+            "-b",
+            "My Board",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Create {
+                    list,
+                    name,
+                    description,
+                    position,
+                    board,
+                    ..
+                } => {
+                    assert_eq!(list, "list123");
+                    assert_eq!(name, Some("Card name".to_string()));
+                    assert_eq!(description, Some("desc".to_string()));
+                    assert_eq!(position, Some("top".to_string()));
+                    assert_eq!(board, Some("My Board".to_string()));
+                }
+                _ => panic!("Expected Create command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_create_copy_description_from() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "list123",
+            "Card name",
+            "--copy-description-from",
+            "Release checklist",
+            "-d",
+            "desc",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Create {
+                    description,
+                    copy_description_from,
+                    ..
+                } => {
+                    // Both are accepted; --description wins when the card is created
+                    assert_eq!(description, Some("desc".to_string()));
+                    assert_eq!(copy_description_from, Some("Release checklist".to_string()));
+                }
+                _ => panic!("Expected Create command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_create_with_labels_and_members() {
+        let cli = Cli::try_parse_from([
+            "trello", "card", "create", "To Do", "Foo", "-l", "Bug", "-l", "Urgent", "-m", "alice",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Create { label, member, .. } => {
+                    assert_eq!(label, vec!["Bug", "Urgent"]);
+                    assert_eq!(member, vec!["alice"]);
+                }
+                _ => panic!("Expected Create command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn test_find_board_member() {
+        let members = vec![
+            BoardMember {
+                id: "m1".to_string(),
+                username: "alice".to_string(),
+                full_name: Some("Alice Smith".to_string()),
+                member_type: "admin".to_string(),
+            },
+            BoardMember {
+                id: "m2".to_string(),
+                username: "bob".to_string(),
+                full_name: None,
+                member_type: "normal".to_string(),
+            },
+        ];
+        assert_eq!(find_board_member(&members, "alice").unwrap().id, "m1");
+        assert_eq!(find_board_member(&members, "@Bob").unwrap().id, "m2");
+        assert_eq!(find_board_member(&members, "alice smith").unwrap().id, "m1");
+        assert!(find_board_member(&members, "carol").is_err());
+    }
+
+    #[test]
+    fn test_find_board_label() {
+        let labels = vec![Label {
+            id: "l1".to_string(),
+            name: "Bug".to_string(),
+            color: Some("red".to_string()),
+        }];
+        assert_eq!(find_board_label(&labels, "bug").unwrap().id, "l1");
+        let err = find_board_label(&labels, "Bgu").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::NotFound);
+    }
+
+    #[test]
+    fn test_read_value_from_source() {
+        assert_eq!(
+            read_value_from_source("literal text", io::empty()).unwrap(),
+            "literal text"
+        );
+        assert_eq!(
+            read_value_from_source("-", "from stdin\n".as_bytes()).unwrap(),
+            "from stdin\n"
+        );
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("desc.md");
+        fs::write(&path, "# Heading\n\nBody").unwrap();
+        let value = format!("@{}", path.display());
+        assert_eq!(
+            read_value_from_source(&value, io::empty()).unwrap(),
+            "# Heading\n\nBody"
+        );
+
+        let binary = temp_dir.path().join("binary.bin");
+        fs::write(&binary, [0x66, 0x6f, 0xff, 0xfe]).unwrap();
+        let err =
+            read_value_from_source(&format!("@{}", binary.display()), io::empty()).unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"), "got {}", err);
+        assert!(read_file_value(&path).is_ok());
+
+        let missing = format!("@{}", temp_dir.path().join("missing.md").display());
+        let err = read_value_from_source(&missing, io::empty()).unwrap_err();
+        assert!(err.to_string().contains("missing.md"), "got {}", err);
+    }
+
+    #[test]
+    fn parse_card_create_list_name_substring() {
+        let cli = Cli::try_parse_from(["trello", "card", "create", "To Do", "Card name"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Create { list, .. } => {
+                    assert_eq!(list, "To Do");
+                }
+                _ => panic!("Expected Create command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_create_template_file() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "Todo",
+            "--template-file",
+            "bug.toml",
+            "--var",
+            "SPRINT=42",
+            "--var",
+            "TITLE=a=b",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Create {
+                        name,
+                        template_file,
+                        var,
+                        ..
+                    },
+            } => {
+                assert_eq!(name, None);
+                assert_eq!(template_file, Some(PathBuf::from("bug.toml")));
+                assert_eq!(
+                    var,
+                    vec![
+                        ("SPRINT".to_string(), "42".to_string()),
+                        ("TITLE".to_string(), "a=b".to_string()),
+                    ]
+                );
+            }
+            _ => panic!("Expected Card Create command"),
+        }
+
+        // The name is only optional with a template, and --var needs one
+        assert!(Cli::try_parse_from(["trello", "card", "create", "Todo"]).is_err());
+        assert!(
+            Cli::try_parse_from(["trello", "card", "create", "Todo", "Name", "--var", "A=1"])
+                .is_err()
+        );
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "create",
+                "Todo",
+                "--template-file",
+                "t.toml",
+                "--var",
+                "=1",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn apply_variables_replaces_each_key() {
+        let vars = HashMap::from([
+            ("SPRINT".to_string(), "42".to_string()),
+            ("AREA".to_string(), "api".to_string()),
+        ]);
+        assert_eq!(
+            apply_variables("[{AREA}] Sprint {SPRINT}: {SPRINT}", &vars),
+            "[api] Sprint 42: 42"
+        );
+        assert_eq!(apply_variables("{OTHER}", &vars), "{OTHER}");
+    }
+
+    #[test]
+    fn apply_variables_does_not_expand_substituted_values() {
+        let vars = HashMap::from([
+            ("A".to_string(), "{B}".to_string()),
+            ("B".to_string(), "{A}".to_string()),
+        ]);
+        assert_eq!(apply_variables("{A} {B}", &vars), "{B} {A}");
+
+        let template = parse_card_template("name = \"{A}\"", Path::new("t.toml"), &vars).unwrap();
+        assert_eq!(template.name.as_deref(), Some("{B}"));
+    }
+
+    #[test]
+    fn parse_card_template_toml_and_json() {
+        let vars = HashMap::from([("SPRINT".to_string(), "42".to_string())]);
+        let toml = "name = \"Bug: sprint {SPRINT}\"\n\
+                    description = \"Steps to reproduce\"\n\
+                    position = \"top\"\n\
+                    labels = [\"Bug\", \"Sprint {SPRINT}\"]\n\
+                    due = \"2024-03-08\"\n";
+        let template = parse_card_template(toml, Path::new("bug.toml"), &vars).unwrap();
+        assert_eq!(
+            template,
+            CardTemplate {
+                name: Some("Bug: sprint 42".to_string()),
+                description: Some("Steps to reproduce".to_string()),
+                position: Some("top".to_string()),
+                labels: vec!["Bug".to_string(), "Sprint 42".to_string()],
+                due: Some("2024-03-08".to_string()),
+            }
+        );
+
+        let json = r#"{"name": "Feature {SPRINT}", "labels": ["Feature"]}"#;
+        let template = parse_card_template(json, Path::new("feature.JSON"), &vars).unwrap();
+        assert_eq!(template.name.as_deref(), Some("Feature 42"));
+        assert_eq!(template.labels, vec!["Feature".to_string()]);
+        assert_eq!(template.description, None);
+    }
+
+    #[test]
+    fn parse_card_template_errors() {
+        let vars = HashMap::new();
+        let err = parse_card_template(
+            "name = \"{AREA}: {TITLE}\"\nlabels = [\"{AREA}\"]",
+            Path::new("t.toml"),
+            &vars,
+        )
+        .unwrap_err();
+        assert_eq!(error::exit_code(&err), error::ExitCode::UserError);
+        assert!(err.to_string().contains("{AREA}, {TITLE}"), "got {}", err);
+
+        let err = parse_card_template("title = \"x\"", Path::new("t.toml"), &vars).unwrap_err();
+        assert_eq!(error::exit_code(&err), error::ExitCode::UserError);
+        let err = parse_card_template("{", Path::new("t.json"), &vars).unwrap_err();
+        assert!(err.to_string().contains("t.json"), "got {}", err);
+    }
+
+    #[test]
+    fn parse_card_update_label() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-l", "Bug"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update { card_id, label, .. } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(label, vec!["Bug"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_multiple_labels() {
+        let cli = Cli::try_parse_from([
+            "trello", "card", "update", "abc123", "-l", "Bug", "-l", "Urgent",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update { card_id, label, .. } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(label, vec!["Bug", "Urgent"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_clear_label() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "--clear-label", "Bug"])
+                .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    clear_label,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(clear_label, vec!["Bug"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_multiple_clear_labels() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "--clear-label",
+            "Bug",
+            "--clear-label",
+            "Urgent",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    clear_label,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(clear_label, vec!["Bug", "Urgent"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_comment() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "-c", "A comment"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id, comment, ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(comment, vec!["A comment"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_archive() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-a"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id, archive, ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert!(archive);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_restore() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-r"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    archive,
+                    restore,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert!(!archive);
+                    assert!(restore);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_multiple_comments() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "-c",
+            "Step 1 done",
+            "--comment",
+            "Step 2 done",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update { comment, .. } => {
+                    assert_eq!(comment, vec!["Step 1 done", "Step 2 done"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_comment_and_archive() {
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-c", "Done", "-a"])
+            .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    comment,
+                    archive,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(comment, vec!["Done"]);
+                    assert!(archive);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_label_and_clear_label() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "-l",
+            "green",
+            "--clear-label",
+            "red",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    label,
+                    clear_label,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(label, vec!["green"]);
+                    assert_eq!(clear_label, vec!["red"]);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_no_flags_parses_ok() {
+        // Clap should parse successfully even with no flags; runtime check rejects it
+        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    card_id,
+                    description,
+                    label,
+                    clear_label,
+                    comment,
+                    archive,
+                    restore,
+                    ..
+                } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(description, None);
+                    assert!(label.is_empty());
+                    assert!(clear_label.is_empty());
+                    assert!(comment.is_empty());
+                    assert!(!archive);
+                    assert!(!restore);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_cover_color() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "update", "abc123", "--cover-color", "red"])
+                .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    cover_color,
+                    clear_cover,
+                    ..
+                } => {
+                    assert_eq!(cover_color, Some("red".to_string()));
+                    assert!(!clear_cover);
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_cover_color_rejects_unknown_color() {
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "abc123",
+            "--cover-color",
+            "chartreuse",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_update_position_with_label() {
+        let cli = Cli::try_parse_from([
+            "trello", "card", "update", "abc123", "-l", "Bug", "-p", "top",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update {
+                    label, position, ..
+                } => {
+                    assert_eq!(label, vec!["Bug"]);
+                    assert_eq!(position, Some("top".to_string()));
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_update_by_name_with_board() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "update",
+            "login bug",
+            "-b",
+            "Work",
+            "-c",
+            "Done",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Update { card_id, board, .. } => {
+                    assert_eq!(card_id, "login bug");
+                    assert_eq!(board, Some("Work".to_string()));
+                }
+                _ => panic!("Expected Update command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_move() {
+        let cli = Cli::try_parse_from(["trello", "card", "move", "abc123", "top"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Move { card_id, position } => {
+                    assert_eq!(card_id, "abc123");
+                    assert_eq!(position, "top");
+                }
+                _ => panic!("Expected Move command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_delete() {
+        let cli = Cli::try_parse_from(["trello", "card", "delete", "abc123"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Delete { card_id } => {
+                    assert_eq!(card_id, "abc123");
+                }
+                _ => panic!("Expected Delete command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_move() {
+        let cli = Cli::try_parse_from(["trello", "list", "move", "list456", "bottom"]).unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Move { list_id, position } => {
+                    assert_eq!(list_id, "list456");
+                    assert_eq!(position, "bottom");
+                }
+                _ => panic!("Expected Move command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_show() {
+        let cli = Cli::try_parse_from(["trello", "list", "show", "list456"]).unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Show { list, board, json } => {
+                    assert_eq!(list, "list456");
+                    assert_eq!(board, None);
+                    assert!(!json);
+                }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_show_with_json() {
+        let cli = Cli::try_parse_from(["trello", "list", "show", "list456", "--json"]).unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Show { list, json, .. } => {
+                    assert_eq!(list, "list456");
+                    assert!(json);
+                }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_show_by_name_on_board() {
+        let cli = Cli::try_parse_from(["trello", "list", "show", "To Do", "-b", "Work"]).unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Show { list, board, .. } => {
+                    assert_eq!(list, "To Do");
+                    assert_eq!(board, Some("Work".to_string()));
+                }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_find() {
+        let cli = Cli::try_parse_from(["trello", "list", "find", "done", "-b", "Work", "--json"])
+            .unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Find {
+                    pattern,
+                    board,
+                    json,
+                } => {
+                    assert_eq!(pattern, "done");
+                    assert_eq!(board, Some("Work".to_string()));
+                    assert!(json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_list_result_serialization() {
+        let result = ListResult {
+            id: "507f1f77bcf86cd799439011".to_string(),
+            board: "Work".to_string(),
+            name: "Done".to_string(),
+        };
+        let parsed = serde_json::to_value(&result).unwrap();
+        assert_eq!(parsed["id"], "507f1f77bcf86cd799439011");
+        assert_eq!(parsed["board"], "Work");
+        assert_eq!(parsed["name"], "Done");
+    }
+
+    #[test]
+    fn parse_list_cards() {
+        let cli = Cli::try_parse_from(["trello", "list", "cards", "To Do", "-b", "Work"]).unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Cards {
+                    list,
+                    board,
+                    json,
+                    archived,
+                    sort,
+                    sort_desc,
+                    nulls_first,
+                } => {
+                    assert_eq!(sort, SortField::Pos);
+                    assert!(!sort_desc);
+                    assert!(!nulls_first);
+                    assert_eq!(list, "To Do");
+                    assert_eq!(board, Some("Work".to_string()));
+                    assert!(!json);
+                    assert!(!archived);
+                }
+                _ => panic!("Expected Cards command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_card_result_omits_list_fields_when_none() {
+        let result = CardResult {
+            closed: true,
+            ..card_result("1", "Board", "List")
+        };
+        let parsed = serde_json::to_value(&result).unwrap();
+        let obj = parsed.as_object().unwrap();
+        assert!(!obj.contains_key("closed"));
+        assert!(!obj.contains_key("list_id"));
+        assert!(!obj.contains_key("pos"));
+        assert!(!obj.contains_key("labels"));
+        assert!(!obj.contains_key("description"));
+        assert!(obj.contains_key("url"));
+
+        let result = CardResult {
+            pos: Some(16384.0),
+            labels: Some(vec!["Bug".to_string()]),
+            url: Some("https://trello.com/c/abc123".to_string()),
+            description: Some("Steps to reproduce".to_string()),
+            ..result
+        };
+        let parsed = serde_json::to_value(&result).unwrap();
+        assert_eq!(parsed["url"], "https://trello.com/c/abc123");
+        assert_eq!(parsed["pos"], 16384.0);
+        assert_eq!(parsed["labels"][0], "Bug");
+        assert_eq!(parsed["description"], "Steps to reproduce");
+    }
+
+    #[test]
+    fn test_description_preview() {
+        let exactly_100 = "a".repeat(100);
+        assert_eq!(description_preview(&exactly_100), exactly_100);
+        assert_eq!(
+            description_preview(&"a".repeat(101)),
+            format!("{}...", "a".repeat(100))
+        );
+        // Counted in characters, not bytes
+        assert_eq!(
+            description_preview(&"é".repeat(101)),
+            format!("{}...", "é".repeat(100))
+        );
+        assert_eq!(
+            description_preview("  Steps:\n1. Log in\t2. Crash\n"),
+            "Steps: 1. Log in 2. Crash"
+        );
+        assert_eq!(description_preview(""), "");
+    }
+
+    #[test]
+    fn parse_card_find_show_description() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "find", "bug", "--show-description"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        show_description, ..
+                    },
+            } => assert!(show_description),
+            _ => panic!("Expected Card Find command"),
+        }
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "find",
+                "bug",
+                "--show-description",
+                "--group-by-list"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_exclude_lists() {
+        let results = || {
+            vec![
+                card_result("1", "Work", "To Do"),
+                card_result("2", "Work", "Done"),
+                card_result("3", "Work", "Done (2023)"),
+                card_result("4", "Work", "Archive"),
+            ]
+        };
+        let ids = |results: Vec<CardResult>| results.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        let excluded = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(exclude_lists(results(), &excluded(&["done"]))),
+            ["1", "4"]
+        );
+        assert_eq!(
+            ids(exclude_lists(results(), &excluded(&["Done", "Archive"]))),
+            ["1"]
+        );
+        // Overlapping patterns exclude each card once
+        assert_eq!(
+            ids(exclude_lists(
+                results(),
+                &excluded(&["Done", "Done (2023)"])
+            )),
+            ["1", "4"]
+        );
+        assert_eq!(
+            ids(exclude_lists(results(), &excluded(&["Backlog"]))),
+            ["1", "2", "3", "4"]
+        );
+    }
+
+    #[test]
+    fn check_board_limit_allows_up_to_max() {
+        assert!(check_board_limit(20, 20).is_ok());
+        let err = check_board_limit(21, 20).unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::UserError);
+        assert!(err.to_string().contains("21 boards (limit 20)"));
+        assert!(err.to_string().contains("--confirm-large"));
+    }
+
+    /// Check that every key of `value` is a property of `schema` and every required
+    /// property is present.
+    fn assert_matches_schema(value: &serde_json::Value, schema: &serde_json::Value) {
+        let object = value.as_object().unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        for key in object.keys() {
+            assert!(
+                properties.contains_key(key),
+                "'{}' is not in the schema",
+                key
+            );
+        }
+        for key in schema["required"].as_array().unwrap() {
+            let key = key.as_str().unwrap();
+            assert!(object.contains_key(key), "required '{}' is missing", key);
+            assert!(
+                properties.contains_key(key),
+                "required '{}' has no property",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn card_result_json_schema_matches_output() {
+        let schema = card_result_json_schema();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(schema["type"], "array");
+        let card_schema = &schema["definitions"]["CardResult"];
+
+        let minimal = card_result("1", "Board", "List");
+        assert_matches_schema(&serde_json::to_value(&minimal).unwrap(), card_schema);
+        let full = CardResult {
+            pos: Some(1.0),
+            labels: Some(vec!["Bug".to_string()]),
+            url: Some("https://trello.com/c/abc".to_string()),
+            description: Some("Details".to_string()),
+            ..minimal
+        };
+        let value = serde_json::to_value(&full).unwrap();
+        assert_matches_schema(&value, card_schema);
+        // Every optional property shows up in the full result
+        assert_eq!(
+            value.as_object().unwrap().len(),
+            card_schema["properties"].as_object().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn show_card_result_json_schema_matches_output() {
+        let schema = show_card_result_json_schema();
+        assert_eq!(schema["type"], "object");
+        assert_matches_schema(&serde_json::to_value(template_result()).unwrap(), &schema);
+
+        let full = ShowCardResult {
+            position: Some(PositionInfo {
+                ordinal: 1,
+                total: 2,
+                raw: 16384.0,
+            }),
+            comments: Some(vec![CommentInfo {
+                id: "a1".to_string(),
+                date: "2024-01-01".to_string(),
+                author: "alice".to_string(),
+                text: "Hi".to_string(),
+                highlight_match: None,
+            }]),
+            siblings: Some(vec![]),
+            webhooks: Some(vec![]),
+            ..template_result()
+        };
+        let value = serde_json::to_value(&full).unwrap();
+        assert_matches_schema(&value, &schema);
+        assert_eq!(
+            value.as_object().unwrap().len(),
+            schema["properties"].as_object().unwrap().len()
+        );
+        for (key, item) in [
+            ("position", &value["position"]),
+            ("comments", &value["comments"][0]),
+        ] {
+            let item_schema = &schema["properties"][key];
+            let item_schema = if item_schema["type"] == "array" {
+                &item_schema["items"]
+            } else {
+                item_schema
+            };
+            assert_matches_schema(item, item_schema);
+        }
+    }
+
+    #[test]
+    fn parse_json_schema_without_pattern() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "--json-schema"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Find { json_schema, .. },
+            } => assert!(json_schema),
+            _ => panic!("Expected Card Find command"),
+        }
+        let cli = Cli::try_parse_from(["trello", "card", "show", "--json-schema"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { json_schema, .. },
+            } => assert!(json_schema),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "card", "find"]).is_err());
+        assert!(Cli::try_parse_from(["trello", "card", "show"]).is_err());
+    }
+
+    #[test]
+    fn card_results_json_is_always_an_array() {
+        let result = CardResult {
+            title: "Title".to_string(),
+            ..card_result("1", "Board", "List")
+        };
+        let results = [result];
+
+        let value: serde_json::Value =
+            serde_json::from_str(&card_results_json(&results, 1, 0, 0).unwrap()).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+        assert_eq!(value[0]["title"], "Title");
+
+        let value: serde_json::Value =
+            serde_json::from_str(&card_results_json(&[], 0, 0, 0).unwrap()).unwrap();
+        assert_eq!(value, serde_json::json!([]));
+
+        let value: serde_json::Value =
+            serde_json::from_str(&card_results_json(&results, 3, 2, 1).unwrap()).unwrap();
+        assert_eq!(value["total"], 3);
+        assert_eq!(value["items"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_card_show_compact_requires_json() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "show", "abc", "--json", "--compact"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { json, compact, .. },
+            } => assert!(json && compact),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "card", "show", "abc", "--compact"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "show",
+                "abc",
+                "--json",
+                "--compact",
+                "--comments"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_card_show_webhooks() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc", "--webhooks"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { webhooks, .. },
+            } => assert!(webhooks),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "show",
+                "abc",
+                "--json",
+                "--compact",
+                "--webhooks"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_card_show_highlight_comments() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "show",
+            "abc",
+            "--comments",
+            "--highlight-comments",
+            "PR #\\d+",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Show {
+                        highlight_comments, ..
+                    },
+            } => assert_eq!(highlight_comments.as_deref(), Some("PR #\\d+")),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "show",
+                "abc",
+                "--highlight-comments",
+                "PR"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_show_failures_error_keeps_the_category() {
+        let not_found = || anyhow::Error::from(TrelloError::NotFound("No card".into()));
+        let err = show_failures_error(vec![not_found(), not_found()], 3);
+        assert_eq!(err.to_string(), "Failed to show 2 of 3 cards");
+        assert_eq!(error::exit_code(&err), ExitCode::NotFound);
+
+        let auth = anyhow::Error::from(TrelloError::Auth("invalid token".into()));
+        let err = show_failures_error(vec![auth, not_found()], 2);
+        assert_eq!(error::exit_code(&err), ExitCode::AuthError);
+    }
+
+    #[test]
+    fn parse_card_show_qr() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc", "--qr"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { qr, .. },
+            } => assert!(qr),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "card", "show", "abc", "--qr", "--json"]).is_err());
+        assert!(
+            Cli::try_parse_from(["trello", "card", "show", "abc", "--qr", "--field", "url"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_list_move_cards() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "move-cards",
+            "Doing",
+            "Done",
+            "-b",
+            "Work",
+            "--filter-label",
+            "Bug",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::MoveCards {
+                    src_list,
+                    dst_list,
+                    board,
+                    filter_label,
+                    dry_run,
+                    force,
+                } => {
+                    assert_eq!(src_list, "Doing");
+                    assert_eq!(dst_list, "Done");
+                    assert_eq!(board, Some("Work".to_string()));
+                    assert_eq!(filter_label, Some("Bug".to_string()));
+                    assert!(dry_run);
+                    assert!(!force);
+                }
+                _ => panic!("Expected MoveCards command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_find_reference_card() {
+        let mut cards = vec![
+            test_card("1", "Card 1", 1.0),
+            test_card("2", "Card 2", 2.0),
+            test_card("12", "Card 12", 3.0),
+        ];
+        cards[0].name = "Write docs".to_string();
+        cards[1].name = "Fix login bug".to_string();
+        cards[2].name = "Fix logout bug".to_string();
+
+        assert_eq!(find_reference_card(&cards, "docs").unwrap(), 0);
+        assert_eq!(find_reference_card(&cards, "LOGOUT").unwrap(), 2);
+        // An exact ID wins over name matches
+        assert_eq!(find_reference_card(&cards, "2").unwrap(), 1);
+
+        let err = find_reference_card(&cards, "fix").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::UserError);
+        assert!(
+            err.to_string()
+                .contains("Fix login bug (2), Fix logout bug (12)")
+        );
+
+        let err = find_reference_card(&cards, "deploy").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::NotFound);
+    }
+
+    #[test]
+    fn test_find_reference_list() {
+        let lists = vec![
+            test_list("l1", "To Do"),
+            test_list("l2", "Doing"),
+            test_list("l3", "Done"),
+        ];
+
+        assert_eq!(find_reference_list(&lists, "doing").unwrap(), 1);
+        assert_eq!(find_reference_list(&lists, "l3").unwrap(), 2);
+
+        let err = find_reference_list(&lists, "do").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::UserError);
+        assert!(
+            err.to_string().contains("Multiple lists match 'do'"),
+            "got {}",
+            err
+        );
+
+        let err = find_reference_list(&lists, "Review").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::NotFound);
+        assert_eq!(err.to_string(), "No list matching 'Review' on the board");
+    }
+
+    #[test]
+    fn parse_list_create() {
+        let cli = Cli::try_parse_from(["trello", "list", "create", "Work", "Review"]).unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    ListCommands::Create {
+                        board,
+                        name,
+                        position,
+                        after_list,
+                        before_list,
+                        copy_from,
+                    },
+            } => {
+                assert_eq!(board, "Work");
+                assert_eq!(name, "Review");
+                assert_eq!(position, None);
+                assert_eq!(after_list, None);
+                assert_eq!(before_list, None);
+                assert_eq!(copy_from, None);
+            }
+            _ => panic!("Expected List Create command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "create",
+            "Work",
+            "Review",
+            "--after-list",
+            "Doing",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List {
+                command: ListCommands::Create { after_list, .. },
+            } => assert_eq!(after_list.as_deref(), Some("Doing")),
+            _ => panic!("Expected List Create command"),
+        }
+
+        for flags in [
+            ["-p", "top", "--after-list", "Doing"],
+            ["--after-list", "Doing", "--before-list", "Done"],
+            ["--before-list", "Done", "--copy-from", "Reveiw"],
+            ["--copy-from", "Reveiw", "-p", "top"],
+        ] {
+            let mut args = vec!["trello", "list", "create", "Work", "Review"];
+            args.extend(flags);
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", flags);
+        }
+    }
+
+    #[test]
+    fn parse_card_create_relative_position_flags() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "To Do",
+            "New card",
+            "--after-card",
+            "login",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Create {
+                        after_card,
+                        before_card,
+                        ..
+                    },
+            } => {
+                assert_eq!(after_card.as_deref(), Some("login"));
+                assert_eq!(before_card, None);
+            }
+            _ => panic!("Expected Card Create command"),
+        }
+
+        let base = ["trello", "card", "create", "To Do", "New card"];
+        for args in [
+            &["--after-card", "a", "--before-card", "b"][..],
+            &["--after-card", "a", "-p", "top"],
+            &["--before-card", "b", "-p", "2"],
+        ] {
+            let argv: Vec<&str> = base.iter().chain(args).copied().collect();
+            assert!(Cli::try_parse_from(argv).is_err(), "{:?} should fail", args);
+        }
+    }
+
+    #[test]
+    fn test_find_duplicate_card() {
+        let cards = vec![test_card("1", "Card 1", 1.0), test_card("2", "Card 2", 2.0)];
+        assert_eq!(find_duplicate_card(&cards, "card 2").unwrap().id, "2");
+        assert_eq!(find_duplicate_card(&cards, "CARD 1").unwrap().id, "1");
+        assert!(find_duplicate_card(&cards, "Card").is_none());
+        assert!(find_duplicate_card(&cards, "Card 1 ").is_none());
+        assert!(find_duplicate_card(&[], "Card 1").is_none());
+    }
+
+    #[test]
+    fn parse_card_create_duplicate_flags_conflict() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "To Do",
+            "Fix bug",
+            "--rename-duplicate",
+            "(copy)",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Create {
+                        error_on_duplicate,
+                        rename_duplicate,
+                        ..
+                    },
+            } => {
+                assert!(!error_on_duplicate);
+                assert_eq!(rename_duplicate, Some("(copy)".to_string()));
+            }
+            _ => panic!("Expected Card Create command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "create",
+            "To Do",
+            "Fix bug",
+            "--error-on-duplicate",
+            "--rename-duplicate",
+            "(copy)",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_list_cards_sort() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "cards",
+            "To Do",
+            "--sort",
+            "date_last_activity",
+            "--sort-desc",
+            "--nulls-first",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::Cards {
+                    sort,
+                    sort_desc,
+                    nulls_first,
+                    ..
+                } => {
+                    assert_eq!(sort, SortField::DateLastActivity);
+                    assert!(sort_desc);
+                    assert!(nulls_first);
+                }
+                _ => panic!("Expected Cards command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+
+        let result = Cli::try_parse_from(["trello", "list", "cards", "To Do", "--sort", "size"]);
+        assert!(result.is_err());
+    }
+
+    fn sorted_ids(
+        mut cards: Vec<Card>,
+        field: SortField,
+        desc: bool,
+        nulls_first: bool,
+    ) -> Vec<String> {
+        sort_cards(&mut cards, field, desc, nulls_first);
+        cards.into_iter().map(|c| c.id).collect()
+    }
+
+    #[test]
+    fn test_sort_cards_by_pos_and_name() {
+        let mut a = test_card("a", "Card a", 300.0);
+        a.name = "banana".to_string();
+        let mut b = test_card("b", "Card b", 100.0);
+        b.name = "Cherry".to_string();
+        let mut c = test_card("c", "Card c", 200.0);
+        c.name = "apple".to_string();
+        let cards = vec![a, b, c];
+
+        assert_eq!(
+            sorted_ids(cards.clone(), SortField::Pos, false, false),
+            ["b", "c", "a"]
+        );
+        assert_eq!(
+            sorted_ids(cards.clone(), SortField::Pos, true, false),
+            ["a", "c", "b"]
+        );
+        assert_eq!(
+            sorted_ids(cards.clone(), SortField::Name, false, false),
+            ["c", "a", "b"]
+        );
+        assert_eq!(
+            sorted_ids(cards, SortField::Name, true, false),
+            ["b", "a", "c"]
+        );
+    }
+
+    #[test]
+    fn test_sort_cards_by_optional_fields_puts_none_last() {
+        let card = |id: &str, date: Option<&str>| {
+            let mut c = test_card(id, &format!("Card {}", id), 0.0);
+            c.due = date.map(str::to_string);
+            c.date_last_activity = date.map(str::to_string);
+            c
+        };
+        let cards = vec![
+            card("none", None),
+            card("late", Some("2024-03-01T00:00:00.000Z")),
+            card("early", Some("2024-01-01T00:00:00.000Z")),
+        ];
+
+        for field in [SortField::Due, SortField::DateLastActivity] {
+            assert_eq!(
+                sorted_ids(cards.clone(), field, false, false),
+                ["early", "late", "none"]
+            );
+            assert_eq!(
+                sorted_ids(cards.clone(), field, true, false),
+                ["late", "early", "none"]
+            );
+            assert_eq!(
+                sorted_ids(cards.clone(), field, false, true),
+                ["none", "early", "late"]
+            );
+            assert_eq!(
+                sorted_ids(cards.clone(), field, true, true),
+                ["none", "late", "early"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_plan_list_moves_preserves_order() {
+        let cards = vec![
+            test_card("c", "Card c", 300.0),
+            test_card("a", "Card a", 100.0),
+            test_card("b", "Card b", 200.0),
+        ];
+        let ids: Vec<String> = plan_list_moves(cards, None)
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_plan_list_moves_filters_by_label() {
+        let cards = vec![
+            Card {
+                id_labels: vec!["bug".to_string()],
+                ..test_card("c", "Card c", 300.0)
+            },
+            Card {
+                id_labels: vec!["bug".to_string(), "urgent".to_string()],
+                ..test_card("a", "Card a", 100.0)
+            },
+            Card {
+                id_labels: vec!["urgent".to_string()],
+                ..test_card("b", "Card b", 200.0)
+            },
+        ];
+        let ids: Vec<String> = plan_list_moves(cards, Some("bug"))
+            .into_iter()
+            .map(|c| c.id)
+            .collect();
+        assert_eq!(ids, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_render_position_context_short_list() {
+        let cards = vec![
+            test_card("c", "Card c", 300.0),
+            test_card("a", "Card a", 100.0),
+            test_card("b", "Card b", 200.0),
+        ];
+        assert_eq!(
+            render_position_context(&cards, "b", 2),
+            "  1. Card a\n  2. [this card] Card b  ← you are here\n  3. Card c\n"
+        );
+        assert_eq!(render_position_context(&cards, "missing", 2), "");
+    }
+
+    #[test]
+    fn test_render_position_context_elides_distant_cards() {
+        let cards: Vec<Card> = (1..=8)
+            .map(|i| test_card(&i.to_string(), &format!("Card {}", i), f64::from(i)))
+            .collect();
+        assert_eq!(
+            render_position_context(&cards, "5", 1),
+            "  ...\n  4. Card 4\n  5. [this card] Card 5  ← you are here\n  6. Card 6\n  ...\n"
+        );
+        assert_eq!(
+            render_position_context(&cards, "1", 1),
+            "  1. [this card] Card 1  ← you are here\n  2. Card 2\n  ...\n"
+        );
+        assert_eq!(
+            render_position_context(&cards, "8", 1),
+            "  ...\n  7. Card 7\n  8. [this card] Card 8  ← you are here\n"
+        );
+    }
+
+    #[test]
+    fn test_card_ordinal() {
+        let cards = vec![
+            test_card("c", "Card c", 300.0),
+            test_card("a", "Card a", 100.0),
+            test_card("b", "Card b", 200.0),
+        ];
+
+        assert_eq!(
+            card_ordinal(&cards, "a"),
+            Some(PositionInfo {
+                ordinal: 1,
+                total: 3,
+                raw: 100.0
+            })
+        );
+        assert_eq!(card_ordinal(&cards, "b").unwrap().ordinal, 2);
+        assert_eq!(
+            card_ordinal(&cards, "c"),
+            Some(PositionInfo {
+                ordinal: 3,
+                total: 3,
+                raw: 300.0
+            })
+        );
+        assert_eq!(card_ordinal(&cards, "missing"), None);
+    }
+
+    #[test]
+    fn parse_board_show() {
+        let cli = Cli::try_parse_from(["trello", "board", "show", "board123"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::Show { board, json, lists } => {
+                    assert_eq!(board, "board123");
+                    assert!(!json);
+                    assert!(!lists);
+                }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn test_board_detail_counts_cards_per_list() {
+        let details: BoardWithDetails = serde_json::from_str(
+            r#"{"id":"b1","name":"Work","shortUrl":"https://trello.com/b/xyz","closed":false,
+                "lists":[{"id":"l2","name":"Done","idBoard":"b1","pos":2.0},
+                         {"id":"l1","name":"Todo","idBoard":"b1","pos":1.0}],
+                "cards":[{"id":"c1","idList":"l1"},{"id":"c2","idList":"l1"},
+                         {"id":"c3","idList":"l2"}],
+                "members":[{"id":"m1","username":"alice"},{"id":"m2","username":"bob"}]}"#,
+        )
+        .unwrap();
+        let detail = board_detail(details);
+
+        assert_eq!(detail.member_count, 2);
+        let lists: Vec<(&str, usize)> = detail
+            .lists
+            .iter()
+            .map(|l| (l.name.as_str(), l.card_count))
+            .collect();
+        assert_eq!(lists, vec![("Todo", 2), ("Done", 1)]);
+
+        let json = serde_json::to_value(&detail).unwrap();
+        assert_eq!(json["short_url"], "https://trello.com/b/xyz");
+        assert_eq!(json["lists"][0]["card_count"], 2);
+    }
+
+    #[test]
+    fn parse_board_show_with_json() {
+        let cli =
+            Cli::try_parse_from(["trello", "board", "show", "Work", "--json", "--lists"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::Show { board, json, lists } => {
+                    assert_eq!(board, "Work");
+                    assert!(json);
+                    assert!(lists);
+                }
+                _ => panic!("Expected Show command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn parse_board_find() {
+        let cli = Cli::try_parse_from(["trello", "board", "find", "^work", "--closed"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::Find {
+                    pattern,
+                    json,
+                    closed,
+                } => {
+                    assert_eq!(pattern, "^work");
+                    assert!(!json);
+                    assert!(closed);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn test_name_matcher_exact_handles_regex_metacharacters() {
+        // Not a valid regex, so the default matcher cannot be built
+        assert!(NameMatcher::new("C++ (template", false, RegexOptions::default()).is_err());
+
+        let matcher =
+            NameMatcher::new("Fix bug: C++ (template", true, RegexOptions::default()).unwrap();
+        assert!(matcher.is_match("Fix bug: C++ (template"));
+        assert!(matcher.is_match("FIX BUG: c++ (TEMPLATE"));
+        assert!(!matcher.is_match("Fix bug: C++ (template) issue"));
+
+        let matcher = NameMatcher::new("a.b*", true, RegexOptions::default()).unwrap();
+        assert!(matcher.is_match("A.B*"));
+        assert!(!matcher.is_match("aXbbb"));
+    }
+
+    #[test]
+    fn test_name_matcher_case_sensitivity() {
+        let matcher = NameMatcher::new("^Bug", false, RegexOptions::default()).unwrap();
+        assert!(matcher.is_match("bug report"));
+
+        let case_sensitive = RegexOptions {
+            case_sensitive: true,
+            ..RegexOptions::default()
+        };
+        let matcher = NameMatcher::new("^Bug", false, case_sensitive).unwrap();
+        assert!(matcher.is_match("Bug report"));
+        assert!(!matcher.is_match("bug report"));
+        assert!(!matcher.is_match("BUG report"));
+    }
+
+    #[test]
+    fn test_name_matcher_whole_word_and_multiline() {
+        let whole_word = RegexOptions {
+            whole_word: true,
+            ..RegexOptions::default()
+        };
+        let matcher = NameMatcher::new("bug|fix", false, whole_word).unwrap();
+        assert!(matcher.is_match("Bug in login"));
+        assert!(matcher.is_match("Quick fix"));
+        assert!(!matcher.is_match("Debugging notes"));
+        assert!(!matcher.is_match("Prefix handling"));
+
+        let matcher = NameMatcher::new("^Step 2$", false, RegexOptions::default()).unwrap();
+        assert!(!matcher.is_match("Step 1\nStep 2\nStep 3"));
+        let multiline = RegexOptions {
+            multiline: true,
+            ..RegexOptions::default()
+        };
+        let matcher = NameMatcher::new("^Step 2$", false, multiline).unwrap();
+        assert!(matcher.is_match("Step 1\nStep 2\nStep 3"));
+    }
+
+    #[test]
+    fn parse_card_find_exact_conflicts_with_case_sensitive() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "C++", "--exact"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find { exact, .. } => assert!(exact),
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "C++",
+            "--exact",
+            "--case-sensitive",
+        ]);
+        assert!(result.is_err());
+        for flag in ["--multiline", "--whole-word"] {
+            assert!(
+                Cli::try_parse_from(["trello", "card", "find", "C++", "--exact", flag]).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_name_regex_is_case_insensitive() {
+        let regex = build_name_regex("^work").unwrap();
+        assert!(regex.is_match("Work Projects"));
+        assert!(!regex.is_match("Homework"));
+        assert!(build_name_regex("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_format_regex_error() {
+        let message = |pattern: &str| {
+            let err = Regex::new(pattern).unwrap_err();
+            format_regex_error(pattern, &err)
+        };
+
+        assert_eq!(
+            message("foo[bar"),
+            "Invalid regex pattern: 'foo[bar'\n       foo[bar\n          ^  unclosed character class"
+        );
+        assert_eq!(
+            message("fix (login"),
+            "Invalid regex pattern: 'fix (login'\n       fix (login\n           ^  unclosed group"
+        );
+        assert_eq!(
+            message("fix) login"),
+            "Invalid regex pattern: 'fix) login'\n       fix) login\n          ^  unopened group"
+        );
+        assert_eq!(
+            message(r"bug\q"),
+            "Invalid regex pattern: 'bug\\q'\n       bug\\q\n          ^  unrecognized escape sequence"
+        );
+    }
+
+    #[test]
+    fn test_regex_errors_point_into_the_typed_pattern() {
+        let whole_word = RegexOptions {
+            whole_word: true,
+            ..RegexOptions::default()
+        };
+        let Err(err) = NameMatcher::new("a[b", false, whole_word) else {
+            panic!("expected an invalid pattern");
+        };
+        assert_eq!(error::exit_code(&err), ExitCode::UserError);
+        assert_eq!(
+            err.to_string(),
+            "Invalid regex pattern: 'a[b'\n       a[b\n        ^  unclosed character class"
+        );
+    }
+
+    #[test]
+    fn parse_list_delete_archived_cards() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "delete-archived-cards",
+            "Done",
+            "-b",
+            "Work",
+            "--dry-run",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List { command } => match command {
+                ListCommands::DeleteArchivedCards {
+                    list_id,
+                    board,
+                    dry_run,
+                    force,
+                } => {
+                    assert_eq!(list_id, "Done");
+                    assert_eq!(board, Some("Work".to_string()));
+                    assert!(dry_run);
+                    assert!(!force);
+                }
+                _ => panic!("Expected DeleteArchivedCards command"),
+            },
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn parse_board_archive_and_restore_all_lists() {
+        let cli =
+            Cli::try_parse_from(["trello", "board", "archive-all-lists", "Work", "-f"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::ArchiveAllLists { board, force } => {
+                    assert_eq!(board, "Work");
+                    assert!(force);
+                }
+                _ => panic!("Expected ArchiveAllLists command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+
+        let cli = Cli::try_parse_from(["trello", "board", "restore-all-lists", "Work"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::RestoreAllLists { board } => assert_eq!(board, "Work"),
+                _ => panic!("Expected RestoreAllLists command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn parse_board_close_and_reopen() {
+        let cli = Cli::try_parse_from(["trello", "board", "close", "Old project", "-f"]).unwrap();
+        match cli.command {
+            Commands::Board {
+                command: BoardCommands::Close { board, force },
+            } => {
+                assert_eq!(board, "Old project");
+                assert!(force);
+            }
+            _ => panic!("Expected Board Close command"),
+        }
+
+        let cli = Cli::try_parse_from(["trello", "board", "reopen", "Old project"]).unwrap();
+        match cli.command {
+            Commands::Board {
+                command: BoardCommands::Reopen { board },
+            } => assert_eq!(board, "Old project"),
+            _ => panic!("Expected Board Reopen command"),
+        }
+    }
+
+    fn test_board(id: &str, date: Option<&str>) -> Board {
+        Board {
+            id: id.to_string(),
+            name: id.to_string(),
+            date_last_activity: date.map(str::to_string),
+            short_url: None,
+            closed: false,
+            id_organization: None,
+        }
+    }
+
+    fn test_list(id: &str, name: &str) -> List {
+        List {
+            id: id.to_string(),
+            name: name.to_string(),
+            id_board: "b".to_string(),
+            pos: 0.0,
+            closed: false,
+        }
+    }
+
+    #[test]
+    fn test_for_each_list_applies_action_to_every_list() {
+        let lists = vec![
+            test_list("l1", "To Do"),
+            test_list("l2", "Doing"),
+            test_list("l3", "Done"),
+        ];
+        let mut archived = Vec::new();
+        let count = for_each_list(&lists, "Archived", |l| {
+            archived.push(l.id.clone());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(archived, ["l1", "l2", "l3"]);
+    }
+
+    #[test]
+    fn test_template_lists_are_created_in_board_order() {
+        let list = |id: &str, name: &str, pos: f64| List {
+            pos,
+            ..test_list(id, name)
+        };
+        let template = vec![
+            list("t3", "Done", 3000.0),
+            list("t1", "Backlog", 1000.0),
+            list("t2", "Doing", 2000.0),
+        ];
+        let dest = [list("d1", "Inbox", 500.0)];
+
+        // Simulate creating each template list at the bottom of the destination board
+        let mut dest_names: Vec<String> = dest.iter().map(|l| l.name.clone()).collect();
+        let count = for_each_list(&sort_lists_by_pos(template), "Created", |l| {
+            dest_names.push(l.name.clone());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(dest_names, ["Inbox", "Backlog", "Doing", "Done"]);
+    }
+
+    #[test]
+    fn parse_list_create_from_template() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "create-from-template",
+            "Template",
+            "Project X",
+            "--include-cards",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    ListCommands::CreateFromTemplate {
+                        template_board,
+                        dest_board,
+                        include_cards,
+                    },
+            } => {
+                assert_eq!(template_board, "Template");
+                assert_eq!(dest_board, "Project X");
+                assert!(include_cards);
+            }
+            _ => panic!("Expected List CreateFromTemplate command"),
+        }
+    }
+
+    #[test]
+    fn parse_list_copy() {
+        let cli = Cli::try_parse_from(["trello", "list", "copy", "Backlog", "Project X"]).unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    ListCommands::Copy {
+                        src_list,
+                        dst_board,
+                        board,
+                        new_name,
+                        include_cards,
+                    },
+            } => {
+                assert_eq!(src_list, "Backlog");
+                assert_eq!(dst_board, "Project X");
+                assert_eq!(board, None);
+                assert_eq!(new_name, None);
+                assert!(!include_cards);
+            }
+            _ => panic!("Expected List Copy command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "trello",
+            "list",
+            "copy",
+            "Backlog",
+            "Project X",
+            "-b",
+            "Template",
+            "--new-name",
+            "Ideas",
+            "--include-cards",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::List {
+                command:
+                    ListCommands::Copy {
+                        board,
+                        new_name,
+                        include_cards,
+                        ..
+                    },
+            } => {
+                assert_eq!(board.as_deref(), Some("Template"));
+                assert_eq!(new_name.as_deref(), Some("Ideas"));
+                assert!(include_cards);
+            }
+            _ => panic!("Expected List Copy command"),
+        }
+    }
+
+    #[test]
+    fn test_for_each_list_stops_on_error() {
+        let lists = vec![test_list("l1", "To Do"), test_list("l2", "Doing")];
+        let mut attempted = 0;
+        let result = for_each_list(&lists, "Archived", |_| {
+            attempted += 1;
+            anyhow::bail!("API request failed")
+        });
+        assert!(result.is_err());
+        assert_eq!(attempted, 1);
+    }
+
+    #[test]
+    fn parse_board_members_list() {
+        let cli = Cli::try_parse_from(["trello", "board", "members", "list", "Work"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::Members {
+                    command: BoardMemberCommands::List { board },
+                } => {
+                    assert_eq!(board, "Work");
+                }
+                _ => panic!("Expected Members List command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn parse_board_members_invite_with_role() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "board",
+            "members",
+            "invite",
+            "Work",
+            "alice@example.com",
+            "--role",
+            "observer",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::Members {
+                    command: BoardMemberCommands::Invite { board, email, role },
+                } => {
+                    assert_eq!(board, "Work");
+                    assert_eq!(email, "alice@example.com");
+                    assert_eq!(role, Some("observer".to_string()));
+                }
+                _ => panic!("Expected Members Invite command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_minimal() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "bug"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    pattern,
+                    board,
+                    list,
+                    json,
+                    ..
+                } => {
+                    assert_eq!(pattern, "bug");
+                    assert!(board.is_empty());
+                    assert_eq!(list, None);
+                    assert!(!json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_with_board() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "task", "-b", "board"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    pattern,
+                    board,
+                    list,
+                    json,
+                    ..
+                } => {
+                    assert_eq!(pattern, "task");
+                    assert_eq!(board, vec!["board"]);
+                    assert_eq!(list, None);
+                    assert!(!json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_with_list() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "urgent", "-l", "list"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    pattern,
+                    board,
+                    list,
+                    json,
+                    ..
+                } => {
+                    assert_eq!(pattern, "urgent");
+                    assert!(board.is_empty());
+                    assert_eq!(list, Some("list".to_string()));
+                    assert!(!json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_with_json() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "test", "--json"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    pattern,
+                    board,
+                    list,
+                    json,
+                    ..
+                } => {
+                    assert_eq!(pattern, "test");
+                    assert!(board.is_empty());
+                    assert_eq!(list, None);
+                    assert!(json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_full() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "fix",
+            "-b",
+            "project",
+            "-l",
+            "in-progress",
+            "--json",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    pattern,
+                    board,
+                    list,
+                    json,
+                    ..
+                } => {
+                    assert_eq!(pattern, "fix");
+                    assert_eq!(board, vec!["project"]);
+                    assert_eq!(list, Some("in-progress".to_string()));
+                    assert!(json);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_with_pagination() {
+        let cli = Cli::try_parse_from([
+            "trello", "card", "find", "bug", "--limit", "10", "--offset", "20", "-q",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    limit,
+                    offset,
+                    quiet,
+                    ..
+                } => {
+                    assert_eq!(limit, 10);
+                    assert_eq!(offset, 20);
+                    assert!(quiet);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_count() {
+        for flag in ["--count", "--output-count"] {
+            let cli = Cli::try_parse_from(["trello", "card", "find", "bug", flag]).unwrap();
+            match cli.command {
+                Commands::Card { command } => match command {
+                    CardCommands::Find { count, .. } => assert!(count),
+                    _ => panic!("Expected Find command"),
+                },
+                _ => panic!("Expected Card command"),
+            }
+        }
+
+        assert!(
+            Cli::try_parse_from(["trello", "card", "find", "bug", "--count", "--json"]).is_ok()
+        );
+        for conflicting in [
+            &["--ndjson"][..],
+            &["-i"],
+            &["--limit", "5"],
+            &["--show-url"],
+        ] {
+            let mut args = vec!["trello", "card", "find", "bug", "--count"];
+            args.extend_from_slice(conflicting);
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", conflicting);
+        }
+    }
+
+    #[test]
+    fn parse_card_find_quiet_on_empty() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "find", "bug", "--quiet-on-empty"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find { quiet_on_empty, .. } => assert!(quiet_on_empty),
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn parse_card_find_interactive_conflicts_with_json() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "", "-b", "Work", "-i"]).unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find { interactive, .. } => assert!(interactive),
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+
+        let result = Cli::try_parse_from(["trello", "card", "find", "bug", "-i", "--json"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_find_board_scope() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "bug"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        board_filter,
+                        all_boards,
+                        organization,
+                        ..
+                    },
+            } => {
+                assert_eq!(board_filter, "open");
+                assert!(!all_boards);
+                assert_eq!(organization, None);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "bug",
+            "--board-filter",
+            "starred",
+            "--organization",
+            "acme",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        board_filter,
+                        organization,
+                        include_org_boards,
+                        ..
+                    },
+            } => {
+                assert_eq!(board_filter, "starred");
+                assert_eq!(organization, Some("acme".to_string()));
+                assert!(!include_org_boards);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["trello", "card", "find", "bug", "--include-org-boards"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        include_org_boards, ..
+                    },
+            } => assert!(include_org_boards),
+            _ => panic!("Expected Card Find command"),
+        }
+
+        for args in [
+            &["--board-filter", "bogus"][..],
+            &["--include-org-boards", "--board-filter", "starred"],
+            &["--all-boards", "--board-filter", "open"],
+            &[
+                "--organization",
+                "acme",
+                "--board-id",
+                "507f1f77bcf86cd799439011",
+            ],
+        ] {
+            let mut argv = vec!["trello", "card", "find", "bug"];
+            argv.extend_from_slice(args);
+            assert!(Cli::try_parse_from(argv).is_err(), "{:?} should fail", args);
+        }
+    }
+
+    #[test]
+    fn test_picker_label() {
+        let result = CardResult {
+            title: "Fix\tbug".to_string(),
+            ..card_result("1", "Work", "To Do")
+        };
+        assert_eq!(picker_label(&result), "[Work/To Do] Fix bug");
+    }
+
+    #[test]
+    fn parse_card_find_board_id_conflicts_with_board() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "bug",
+            "--board-id",
+            "507f1f77bcf86cd799439011",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    board, board_id, ..
+                } => {
+                    assert!(board.is_empty());
+                    assert_eq!(board_id, Some("507f1f77bcf86cd799439011".to_string()));
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "bug",
+            "-b",
+            "Work",
+            "--board-id",
+            "507f1f77bcf86cd799439011",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_find_label_filters() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "",
+            "--label",
+            "Bug",
+            "--label",
+            "Urgent",
+            "--has-label",
+            "Frontend",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    label,
+                    has_label,
+                    no_labels,
+                    ..
+                } => {
+                    assert_eq!(label, vec!["Bug", "Urgent"]);
+                    assert_eq!(has_label, vec!["Frontend"]);
+                    assert!(!no_labels);
+                }
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "",
+            "--no-labels",
+            "--label",
+            "Bug",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_label_filter_all_vs_any() {
+        let labels = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let all = LabelFilter {
+            all: labels(&["Bug", "Urgent"]),
+            ..Default::default()
+        };
+        assert!(all.matches(&labels(&["bug", "Urgent", "Backend"])));
+        assert!(!all.matches(&labels(&["Bug"])));
+
+        let any = LabelFilter {
+            any: labels(&["Bug", "Urgent"]),
+            ..Default::default()
+        };
+        assert!(any.matches(&labels(&["Bug"])));
+        assert!(any.matches(&labels(&["urgent"])));
+        assert!(!any.matches(&labels(&["Backend"])));
+        assert!(!any.matches(&[]));
+
+        assert!(LabelFilter::default().is_empty());
+        assert!(LabelFilter::default().matches(&labels(&["Bug"])));
+    }
+
+    #[test]
+    fn test_card_filter_description() {
+        let with_desc = |has_description| CardFilter {
+            has_description,
+            ..CardFilter::default()
+        };
+        let descs = ["Steps to reproduce", "", "  \n"];
+        let matching = |filter: &CardFilter| -> Vec<bool> {
+            descs
+                .iter()
+                .map(|d| filter.matches_description(d))
+                .collect()
+        };
+
+        assert_eq!(matching(&with_desc(None)), vec![true, true, true]);
+        assert_eq!(matching(&with_desc(Some(true))), vec![true, false, false]);
+        assert_eq!(matching(&with_desc(Some(false))), vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_card_filter_members() {
+        let filter = |assignee| CardFilter {
+            assignee,
+            ..CardFilter::default()
+        };
+        let members = |ids: &[&str]| -> Vec<String> { ids.iter().map(|s| s.to_string()).collect() };
+
+        let mine = filter(Some(AssigneeFilter::Member("me".to_string())));
+        assert!(mine.matches_members(&members(&["other", "me"])));
+        assert!(!mine.matches_members(&members(&["other"])));
+        assert!(!mine.matches_members(&[]));
+
+        let unassigned = filter(Some(AssigneeFilter::Unassigned));
+        assert!(unassigned.matches_members(&[]));
+        assert!(!unassigned.matches_members(&members(&["me"])));
+
+        assert!(filter(None).matches_members(&members(&["anyone"])));
+    }
+
+    #[test]
+    fn test_card_filter_checklists() {
+        let filter = |checklist| CardFilter {
+            checklist,
+            ..CardFilter::default()
+        };
+        let badges = |check_items, check_items_checked| CardBadges {
+            votes: 0,
+            check_items,
+            check_items_checked,
+        };
+        // No badges, no items, some checked, all checked
+        let cards = [
+            None,
+            Some(badges(0, 0)),
+            Some(badges(4, 1)),
+            Some(badges(3, 3)),
+        ];
+        let matching = |filter: &CardFilter| -> Vec<bool> {
+            cards
+                .iter()
+                .map(|b| filter.matches_checklists(b.as_ref()))
+                .collect()
+        };
+
+        assert_eq!(matching(&filter(None)), vec![true, true, true, true]);
+        assert_eq!(
+            matching(&filter(Some(ChecklistFilter::Present))),
+            vec![false, false, true, true]
+        );
+        assert_eq!(
+            matching(&filter(Some(ChecklistFilter::Absent))),
+            vec![true, true, false, false]
+        );
+        assert_eq!(
+            matching(&filter(Some(ChecklistFilter::Complete))),
+            vec![false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn parse_card_find_checklist_flags() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "find", "", "--checklist-complete"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        has_checklist,
+                        no_checklist,
+                        checklist_complete,
+                        ..
+                    },
+            } => {
+                assert!(!has_checklist && !no_checklist && checklist_complete);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        for flags in [
+            ["--has-checklist", "--no-checklist"],
+            ["--no-checklist", "--checklist-complete"],
+        ] {
+            let mut args = vec!["trello", "card", "find", ""];
+            args.extend(flags);
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", flags);
+        }
+    }
+
+    #[test]
+    fn parse_card_find_assignment_flags() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "",
+            "--assigned-to-me",
+            "-b",
+            "Work",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        assigned_to_me,
+                        not_assigned,
+                        ..
+                    },
+            } => {
+                assert!(assigned_to_me);
+                assert!(!not_assigned);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "",
+            "--assigned-to-me",
+            "--not-assigned",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_card_find_description_flags() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "", "--no-description"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        has_description,
+                        no_description,
+                        ..
+                    },
+            } => {
+                assert!(!has_description);
+                assert!(no_description);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "",
+            "--has-description",
+            "--no-description",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_label_filter_no_labels() {
+        let filter = LabelFilter {
+            none: true,
+            ..Default::default()
+        };
+        assert!(!filter.is_empty());
+        assert!(filter.matches(&[]));
+        assert!(!filter.matches(&["Bug".to_string()]));
+    }
+
+    #[test]
+    fn parse_card_find_multiple_boards() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "bug",
+            "-b",
+            "Project A",
+            "--board",
+            "Project B",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Find { board, .. },
+            } => assert_eq!(board, vec!["Project A", "Project B"]),
+            _ => panic!("Expected Card Find command"),
+        }
+    }
+
+    #[test]
+    fn test_union_boards_skips_duplicates() {
+        let mut boards = Vec::new();
+        union_boards(
+            &mut boards,
+            vec![test_board("1", None), test_board("2", None)],
+        );
+        union_boards(
+            &mut boards,
+            vec![test_board("2", None), test_board("3", None)],
+        );
+        union_boards(&mut boards, vec![]);
+        let ids: Vec<&str> = boards.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_filter_active_boards() {
+        let boards = vec![
+            test_board("recent", Some("2024-06-10T12:00:00.000Z")),
+            test_board("stale", Some("2024-01-01T00:00:00.000Z")),
+            test_board("unknown", None),
+            test_board("edge", Some("2024-06-01T12:00:00.000Z")),
+        ];
+        let now = DateTime::parse_from_rfc3339("2024-06-11T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (active, skipped) = filter_active_boards(boards, 10, now);
+        let ids: Vec<&str> = active.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, ["recent", "unknown", "edge"]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn parse_card_find_active_within_days() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "find",
+            "bug",
+            "--active-within-days",
+            "30",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card { command } => match command {
+                CardCommands::Find {
+                    active_within_days, ..
+                } => assert_eq!(active_within_days, Some(30)),
+                _ => panic!("Expected Find command"),
+            },
+            _ => panic!("Expected Card command"),
+        }
+    }
+
+    #[test]
+    fn test_cap_per_board_applies_independently_per_board() {
+        let results = vec![
+            card_result("1", "Work", "To Do"),
+            card_result("2", "Work", "To Do"),
+            card_result("3", "Work", "To Do"),
+            card_result("4", "Home", "To Do"),
+            card_result("5", "Side", "To Do"),
+            card_result("6", "Side", "To Do"),
+            // A different board that happens to share a name
+            CardResult {
+                board_id: "other".to_string(),
+                ..card_result("7", "Work", "To Do")
+            },
+        ];
+
+        let (kept, capped) = cap_per_board(results, 2);
+        let ids: Vec<&str> = kept.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["1", "2", "4", "5", "6", "7"]);
+        assert_eq!(capped, ["Work"]);
+    }
+
+    #[test]
+    fn parse_board_list_recent() {
+        let cli = Cli::try_parse_from(["trello", "board", "list", "--recent", "5"]).unwrap();
+        match cli.command {
+            Commands::Board { command } => match command {
+                BoardCommands::List {
+                    recent,
+                    json,
+                    url,
+                    sort,
+                    sort_desc,
+                    with_card_counts,
+                } => {
+                    assert_eq!(recent, Some(5));
+                    assert!(!json);
+                    assert!(!url);
+                    assert_eq!(sort, None);
+                    assert!(!sort_desc);
+                    assert!(!with_card_counts);
+                }
+                _ => panic!("Expected List command"),
+            },
+            _ => panic!("Expected Board command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "board", "list", "--recent", "0"]).is_err());
+        assert!(Cli::try_parse_from(["trello", "board", "list", "--recent", "-3"]).is_err());
+        assert!(Cli::try_parse_from(["trello", "board", "list", "--url", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_board_list_sort_flags() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "board",
+            "list",
+            "--sort",
+            "card-count",
+            "--sort-desc",
+            "--with-card-counts",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Board {
+                command:
+                    BoardCommands::List {
+                        sort,
+                        sort_desc,
+                        with_card_counts,
+                        ..
+                    },
+            } => {
+                assert_eq!(sort, Some(BoardSortField::CardCount));
+                assert!(sort_desc);
+                assert!(with_card_counts);
+            }
+            _ => panic!("Expected board list command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "board", "list", "--sort", "cards"]).is_err());
+        assert!(Cli::try_parse_from(["trello", "board", "list", "--sort-desc"]).is_err());
+        assert!(
+            Cli::try_parse_from(["trello", "board", "list", "--url", "--with-card-counts"])
+                .is_err()
+        );
+    }
+
+    fn board_result(name: &str, activity: Option<&str>, card_count: Option<u32>) -> BoardResult {
+        BoardResult {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            date_last_activity: activity.map(str::to_string),
+            short_url: None,
+            card_count,
+        }
+    }
+
+    fn board_names(boards: &[BoardResult]) -> Vec<&str> {
+        boards.iter().map(|b| b.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_boards_by_name() {
+        let mut boards = vec![
+            board_result("roadmap", None, None),
+            board_result("Inbox", None, None),
+            board_result("Work", None, None),
+        ];
+        sort_boards(&mut boards, BoardSortField::Name, false);
+        assert_eq!(board_names(&boards), ["Inbox", "roadmap", "Work"]);
+        sort_boards(&mut boards, BoardSortField::Name, true);
+        assert_eq!(board_names(&boards), ["Work", "roadmap", "Inbox"]);
+    }
+
+    #[test]
+    fn test_sort_boards_by_activity() {
+        let mut boards = vec![
+            board_result("Never", None, None),
+            board_result("New", Some("2024-05-01T00:00:00.000Z"), None),
+            board_result("Old", Some("2023-01-15T00:00:00.000Z"), None),
+        ];
+        sort_boards(&mut boards, BoardSortField::Activity, false);
+        assert_eq!(board_names(&boards), ["Old", "New", "Never"]);
+        sort_boards(&mut boards, BoardSortField::Activity, true);
+        assert_eq!(board_names(&boards), ["New", "Old", "Never"]);
+    }
+
+    #[test]
+    fn test_sort_boards_by_card_count() {
+        let mut boards = vec![
+            board_result("Medium", None, Some(12)),
+            board_result("Unknown", None, None),
+            board_result("Small", None, Some(3)),
+            board_result("Large", None, Some(40)),
+        ];
+        sort_boards(&mut boards, BoardSortField::CardCount, false);
+        assert_eq!(
+            board_names(&boards),
+            ["Small", "Medium", "Large", "Unknown"]
+        );
+        sort_boards(&mut boards, BoardSortField::CardCount, true);
+        assert_eq!(
+            board_names(&boards),
+            ["Large", "Medium", "Small", "Unknown"]
+        );
+    }
+
+    #[test]
+    fn test_board_result_card_count_json() {
+        let json = serde_json::to_value(board_result("Work", None, Some(7))).unwrap();
+        assert_eq!(json["card_count"], 7);
+        let json = serde_json::to_value(board_result("Work", None, None)).unwrap();
+        assert!(json.get("card_count").is_none());
+    }
+
+    #[test]
+    fn test_most_recent_boards_orders_and_truncates() {
+        let boards = vec![
+            test_board("old", Some("2023-05-01T00:00:00.000Z")),
+            test_board("none", None),
+            test_board("newest", Some("2024-06-10T12:00:00.000Z")),
+            test_board("newer", Some("2024-02-01T08:00:00.000Z")),
+        ];
+
+        let ids = |boards: Vec<Board>| boards.into_iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(most_recent_boards(boards.clone(), 2)),
+            ["newest", "newer"]
+        );
+        assert_eq!(
+            ids(most_recent_boards(boards, 10)),
+            ["newest", "newer", "old", "none"]
+        );
+    }
+
+    #[test]
+    fn output_writer_truncates_or_appends() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("results.tsv");
+
+        for line in ["first", "second"] {
+            let mut out = output_writer(Some(&path), false).unwrap();
+            writeln!(out, "{}", line).unwrap();
+            out.flush().unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+
+        let mut out = output_writer(Some(&path), true).unwrap();
+        writeln!(out, "third").unwrap();
+        out.flush().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\nthird\n");
+
+        let missing_dir = temp_dir.path().join("missing").join("results.tsv");
+        assert!(output_writer(Some(&missing_dir), false).is_err());
+    }
 
-                if json {
-                    let result = serde_json::json!({
-                        "id": board.id,
-                        "name": board.name,
-                    });
-                    println!(
-                        "{}",
-                        serde_json::to_string(&result).context("Failed to serialize result")?
-                    );
-                } else {
-                    println!("Name: {}", board.name);
-                    println!("ID: {}", board.id);
-                }
-            }
-        },
+    fn card_result(id: &str, board: &str, list: &str) -> CardResult {
+        CardResult {
+            id: id.to_string(),
+            board: board.to_string(),
+            list: list.to_string(),
+            title: format!("Card {}", id),
+            board_id: format!("b-{}", board),
+            list_id: "l1".to_string(),
+            closed: false,
+            list_closed: false,
+            pos: None,
+            labels: None,
+            url: None,
+            description: None,
+        }
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_list_label_marks_archived_lists() {
+        let mut result = card_result("c1", "Work", "Todo");
+        assert_eq!(list_label(&result), "Todo");
+        assert_eq!(serde_json::to_value(&result).unwrap()["list_closed"], false);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use clap::CommandFactory;
+        result.list_closed = true;
+        assert_eq!(list_label(&result), "Todo [archived]");
+        assert_eq!(picker_label(&result), "[Work/Todo [archived]] Card c1");
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["list"], "Todo");
+        assert_eq!(json["list_closed"], true);
+    }
 
     #[test]
-    fn verify_cli() {
-        Cli::command().debug_assert();
+    fn parse_card_find_include_closed_lists() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "bug", "--include-closed-lists"])
+            .unwrap();
+        match cli.command {
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        include_closed_lists,
+                        ..
+                    },
+            } => assert!(include_closed_lists),
+            _ => panic!("Expected Card Find command"),
+        }
     }
 
     #[test]
-    fn parse_card_update_description() {
-        let cli = Cli::try_parse_from([
-            "trello",
-            "card",
-            "update",
-            "abc123",
-            "-d",
-            "New description",
-        ])
-        .unwrap();
+    fn test_write_ndjson_plain() {
+        let results = vec![
+            card_result("c1", "Work", "Todo"),
+            card_result("c2", "Work", "Todo"),
+        ];
+        let mut out = Vec::new();
+        write_ndjson(&results, None, &mut out).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["id"], "c1");
+        assert!(lines[0].get("type").is_none());
+    }
+
+    #[test]
+    fn test_write_ndjson_with_meta() {
+        let results = vec![
+            card_result("c1", "Work", "Todo"),
+            card_result("c2", "Work", "Todo"),
+        ];
+        let meta = NdjsonMeta {
+            total: results.len(),
+            query: "bug",
+            boards_searched: 3,
+        };
+        let mut out = Vec::new();
+        write_ndjson(&results, Some(meta), &mut out).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            serde_json::json!({"type": "meta", "total": 2, "query": "bug", "boards_searched": 3})
+        );
+        assert_eq!(lines[1]["type"], "card");
+        assert_eq!(lines[1]["id"], "c1");
+        assert_eq!(lines[2]["title"], "Card c2");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn parse_card_find_ndjson_flags() {
+        let cli = Cli::try_parse_from(["trello", "card", "find", "bug", "--json-lines"]).unwrap();
         match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    description,
-                    label,
-                    clear_label,
-                    comment,
-                    archive,
-                    restore,
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(description, Some("New description".to_string()));
-                    assert!(label.is_empty());
-                    assert!(clear_label.is_empty());
-                    assert_eq!(comment, None);
-                    assert!(!archive);
-                    assert!(!restore);
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        ndjson,
+                        ndjson_with_meta,
+                        ..
+                    },
+            } => {
+                assert!(ndjson);
+                assert!(!ndjson_with_meta);
+            }
+            _ => panic!("Expected Card Find command"),
+        }
+
+        for conflicting in ["--json", "--ndjson"] {
+            let result = Cli::try_parse_from([
+                "trello",
+                "card",
+                "find",
+                "bug",
+                "--ndjson-with-meta",
+                conflicting,
+            ]);
+            assert!(result.is_err(), "{} should conflict", conflicting);
         }
     }
 
     #[test]
-    fn parse_card_create_minimal() {
+    fn parse_card_find_output_template_file() {
         let cli = Cli::try_parse_from([
             "trello",
             "card",
-            "create",
-            "507f1f77bcf86cd799439011",
-            "Card name",
+            "find",
+            "bug",
+            "--output-template-file",
+            "report.md.tmpl",
         ])
         .unwrap();
         match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Create {
-                    list,
-                    name,
-                    description,
-                    position,
-                    board,
-                } => {
-                    assert_eq!(list, "507f1f77bcf86cd799439011");
-                    assert_eq!(name, "Card name");
-                    assert_eq!(description, None);
-                    assert_eq!(position, "bottom");
-                    assert_eq!(board, None);
-                }
-                _ => panic!("Expected Create command"),
-            },
-            _ => panic!("Expected Card command"),
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        output_template_file,
+                        ..
+                    },
+            } => assert_eq!(output_template_file, Some(PathBuf::from("report.md.tmpl"))),
+            _ => panic!("Expected Card Find command"),
+        }
+
+        for flag in ["--json", "--ndjson", "--group-by-list", "--show-url"] {
+            assert!(
+                Cli::try_parse_from([
+                    "trello",
+                    "card",
+                    "find",
+                    "bug",
+                    "--output-template-file",
+                    "t",
+                    flag
+                ])
+                .is_err(),
+                "{} should conflict",
+                flag
+            );
         }
     }
 
     #[test]
-    fn parse_card_create_with_all_optional_flags() {
+    fn parse_card_find_output_file() {
         let cli = Cli::try_parse_from([
             "trello",
             "card",
-            "create",
-            "list123",
-            "Card name",
-            "-d",
-            "desc",
-            "-p",
-            "top",
-            "-b",
-            "My Board",
+            "find",
+            "bug",
+            "--output-file",
+            "out.tsv",
+            "--append",
         ])
         .unwrap();
         match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Create {
-                    list,
-                    name,
-                    description,
-                    position,
-                    board,
-                } => {
-                    assert_eq!(list, "list123");
-                    assert_eq!(name, "Card name");
-                    assert_eq!(description, Some("desc".to_string()));
-                    assert_eq!(position, "top");
-                    assert_eq!(board, Some("My Board".to_string()));
-                }
-                _ => panic!("Expected Create command"),
-            },
-            _ => panic!("Expected Card command"),
+            Commands::Card {
+                command:
+                    CardCommands::Find {
+                        output_file,
+                        append,
+                        ..
+                    },
+            } => {
+                assert_eq!(output_file, Some(PathBuf::from("out.tsv")));
+                assert!(append);
+            }
+            _ => panic!("Expected Card Find command"),
         }
-    }
 
-    #[test]
-    fn parse_card_create_list_name_substring() {
-        let cli = Cli::try_parse_from(["trello", "card", "create", "To Do", "Card name"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Create { list, .. } => {
-                    assert_eq!(list, "To Do");
-                }
-                _ => panic!("Expected Create command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+        assert!(Cli::try_parse_from(["trello", "card", "find", "bug", "--append"]).is_err());
+        assert!(
+            Cli::try_parse_from(["trello", "card", "find", "bug", "--output-file", "x", "-i"])
+                .is_err()
+        );
     }
 
     #[test]
-    fn parse_card_update_label() {
-        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-l", "Bug"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update { card_id, label, .. } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(label, vec!["Bug"]);
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_print_grouped() {
+        let result = |id: &str, board: &str, list: &str, title: &str| CardResult {
+            title: title.to_string(),
+            ..card_result(id, board, list)
+        };
+        let results = vec![
+            result("ghi789", "My Project", "Done", "Deploy to staging"),
+            result("abc123", "My Project", "In Progress", "Fix login bug"),
+            result("xyz000", "Home", "Chores", "Mow\tlawn"),
+            result("def456", "My Project", "In Progress", "Update schema"),
+        ];
+
+        let mut out = Vec::new();
+        print_grouped(&results, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out.lines().collect::<Vec<_>>(),
+            [
+                "Board: Home",
+                "  List: Chores",
+                "    xyz000  Mow lawn",
+                "Board: My Project",
+                "  List: Done",
+                "    ghi789  Deploy to staging",
+                "  List: In Progress",
+                "    abc123  Fix login bug",
+                "    def456  Update schema",
+            ]
+        );
     }
 
     #[test]
-    fn parse_card_update_multiple_labels() {
+    fn parse_card_find_since_id() {
         let cli = Cli::try_parse_from([
-            "trello", "card", "update", "abc123", "-l", "Bug", "-l", "Urgent",
+            "trello",
+            "card",
+            "find",
+            "",
+            "--since-id",
+            "5f1e7a9c8d0b3e2a1c4d6f80",
+            "--print-cursor",
         ])
         .unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Update { card_id, label, .. } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(label, vec!["Bug", "Urgent"]);
+                CardCommands::Find {
+                    since_id,
+                    print_cursor,
+                    ..
+                } => {
+                    assert_eq!(since_id, Some("5f1e7a9c8d0b3e2a1c4d6f80".to_string()));
+                    assert!(print_cursor);
                 }
-                _ => panic!("Expected Update command"),
+                _ => panic!("Expected Find command"),
             },
             _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_card_update_clear_label() {
-        let cli =
-            Cli::try_parse_from(["trello", "card", "update", "abc123", "--clear-label", "Bug"])
-                .unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    clear_label,
-                    ..
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(clear_label, vec!["Bug"]);
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_has_matching_check_item() {
+        let checklists: Vec<Checklist> = serde_json::from_value(serde_json::json!([
+            {"id": "c1", "name": "Release", "checkItems": [
+                {"id": "i1", "name": "Tag build", "state": "complete"},
+                {"id": "i2", "name": "Write release notes", "state": "incomplete"}
+            ]},
+            {"id": "c2", "name": "Empty", "checkItems": []}
+        ]))
+        .unwrap();
+
+        assert!(has_matching_check_item(
+            &checklists,
+            &build_name_regex("release notes").unwrap()
+        ));
+        assert!(has_matching_check_item(
+            &checklists,
+            &build_name_regex("^tag").unwrap()
+        ));
+        assert!(!has_matching_check_item(
+            &checklists,
+            &build_name_regex("deploy").unwrap()
+        ));
+        assert!(!has_matching_check_item(
+            &[],
+            &build_name_regex(".*").unwrap()
+        ));
     }
 
     #[test]
-    fn parse_card_update_multiple_clear_labels() {
+    fn parse_card_find_with_checklist_item() {
         let cli = Cli::try_parse_from([
             "trello",
             "card",
-            "update",
-            "abc123",
-            "--clear-label",
-            "Bug",
-            "--clear-label",
-            "Urgent",
+            "find",
+            ".*",
+            "--with-checklist-item",
+            "notes",
+            "--max-cards-to-check",
+            "20",
         ])
         .unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    clear_label,
+                CardCommands::Find {
+                    with_checklist_item,
+                    max_cards_to_check,
                     ..
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(clear_label, vec!["Bug", "Urgent"]);
+                    assert_eq!(with_checklist_item, Some("notes".to_string()));
+                    assert_eq!(max_cards_to_check, 20);
                 }
-                _ => panic!("Expected Update command"),
+                _ => panic!("Expected Find command"),
             },
             _ => panic!("Expected Card command"),
         }
+
+        let result =
+            Cli::try_parse_from(["trello", "card", "find", "x", "--max-cards-to-check", "5"]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn parse_card_update_comment() {
-        let cli =
-            Cli::try_parse_from(["trello", "card", "update", "abc123", "-c", "A comment"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id, comment, ..
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(comment, Some("A comment".to_string()));
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_no_matches_exit_codes() {
+        assert_eq!(no_matches("No cards found", false), Status::NoMatches);
+        assert_eq!(Status::NoMatches.code() as i32, 2);
+        assert_eq!(no_matches("No cards found", true), Status::Success);
+        assert_eq!(Status::Success.code() as i32, 0);
     }
 
     #[test]
-    fn parse_card_update_archive() {
-        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-a"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id, archive, ..
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(archive);
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_paginate() {
+        let items: Vec<u32> = (1..=5).collect();
+        assert_eq!(paginate(items.clone(), 0, 0), vec![1, 2, 3, 4, 5]);
+        assert_eq!(paginate(items.clone(), 1, 2), vec![2, 3]);
+        assert_eq!(paginate(items.clone(), 2, 0), vec![3, 4, 5]);
+        // Limit larger than remaining
+        assert_eq!(paginate(items.clone(), 3, 10), vec![4, 5]);
+        // Offset beyond total
+        assert!(paginate(items, 10, 2).is_empty());
     }
 
     #[test]
-    fn parse_card_update_restore() {
-        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-r"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    archive,
-                    restore,
-                    ..
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(!archive);
-                    assert!(restore);
-                }
-                _ => panic!("Expected Update command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_pagination_footer() {
+        assert_eq!(pagination_footer(0, 10, 42), "Showing 1–10 of 42");
+        assert_eq!(pagination_footer(40, 2, 42), "Showing 41–42 of 42");
+        assert_eq!(pagination_footer(50, 0, 42), "Showing 0 of 42");
     }
 
     #[test]
-    fn parse_card_update_comment_and_archive() {
-        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123", "-c", "Done", "-a"])
-            .unwrap();
+    fn parse_card_bulk_archive() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "bulk-archive",
+            "^old",
+            "-b",
+            "Work",
+            "--dry-run",
+        ])
+        .unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    comment,
-                    archive,
-                    ..
+                CardCommands::BulkArchive {
+                    pattern,
+                    board,
+                    list,
+                    dry_run,
+                    force,
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(comment, Some("Done".to_string()));
-                    assert!(archive);
+                    assert_eq!(pattern, "^old");
+                    assert_eq!(board, Some("Work".to_string()));
+                    assert_eq!(list, None);
+                    assert!(dry_run);
+                    assert!(!force);
                 }
-                _ => panic!("Expected Update command"),
+                _ => panic!("Expected BulkArchive command"),
             },
             _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_card_update_label_and_clear_label() {
+    fn parse_card_bulk_move() {
         let cli = Cli::try_parse_from([
             "trello",
             "card",
-            "update",
-            "abc123",
+            "bulk-move",
+            "",
+            "--to-list",
+            "Done",
+            "--to-board",
+            "Archive",
             "-l",
-            "green",
-            "--clear-label",
-            "red",
+            "Doing",
+            "-p",
+            "top",
+            "-f",
+            "-q",
         ])
         .unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    label,
-                    clear_label,
-                    ..
+                CardCommands::BulkMove {
+                    pattern,
+                    to_list,
+                    to_board,
+                    board,
+                    list,
+                    position,
+                    dry_run,
+                    force,
+                    quiet,
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(label, vec!["green"]);
-                    assert_eq!(clear_label, vec!["red"]);
+                    assert_eq!(pattern, "");
+                    assert_eq!(to_list, "Done");
+                    assert_eq!(to_board, Some("Archive".to_string()));
+                    assert_eq!(board, None);
+                    assert_eq!(list, Some("Doing".to_string()));
+                    assert_eq!(position, "top");
+                    assert!(!dry_run);
+                    assert!(force);
+                    assert!(quiet);
                 }
-                _ => panic!("Expected Update command"),
+                _ => panic!("Expected BulkMove command"),
             },
             _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_card_update_no_flags_parses_ok() {
-        // Clap should parse successfully even with no flags; runtime check rejects it
-        let cli = Cli::try_parse_from(["trello", "card", "update", "abc123"]).unwrap();
+    fn parse_card_bulk_move_requires_to_list() {
+        let result = Cli::try_parse_from(["trello", "card", "bulk-move", "bug"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_is_affirmative() {
+        assert!(is_affirmative("y"));
+        assert!(is_affirmative("Y"));
+        assert!(is_affirmative(" yes\n"));
+        assert!(!is_affirmative(""));
+        assert!(!is_affirmative("n"));
+        assert!(!is_affirmative("nope"));
+    }
+
+    #[test]
+    fn parse_card_show() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123"]).unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Update {
-                    card_id,
-                    description,
-                    label,
-                    clear_label,
-                    comment,
-                    archive,
-                    restore,
+                CardCommands::Show {
+                    card_ids,
+                    json,
+                    comments,
+                    ..
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(description, None);
-                    assert!(label.is_empty());
-                    assert!(clear_label.is_empty());
-                    assert_eq!(comment, None);
-                    assert!(!archive);
-                    assert!(!restore);
+                    assert_eq!(card_ids, vec!["abc123"]);
+                    assert!(!json);
+                    assert!(!comments);
                 }
-                _ => panic!("Expected Update command"),
+                _ => panic!("Expected Show command"),
             },
             _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_card_move() {
-        let cli = Cli::try_parse_from(["trello", "card", "move", "abc123", "top"]).unwrap();
+    fn parse_card_show_with_json() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--json"]).unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Move { card_id, position } => {
-                    assert_eq!(card_id, "abc123");
-                    assert_eq!(position, "top");
+                CardCommands::Show {
+                    card_ids,
+                    json,
+                    comments,
+                    ..
+                } => {
+                    assert_eq!(card_ids, vec!["abc123"]);
+                    assert!(json);
+                    assert!(!comments);
                 }
-                _ => panic!("Expected Move command"),
+                _ => panic!("Expected Show command"),
             },
             _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_card_delete() {
-        let cli = Cli::try_parse_from(["trello", "card", "delete", "abc123"]).unwrap();
+    fn parse_card_show_multiple_ids() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "show", "abc123", "def456", "--json"]).unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { card_ids, json, .. },
+            } => {
+                assert_eq!(card_ids, vec!["abc123", "def456"]);
+                assert!(json);
+            }
+            _ => panic!("Expected Card Show command"),
+        }
+
+        assert!(Cli::try_parse_from(["trello", "card", "show"]).is_err());
+    }
+
+    #[test]
+    fn parse_card_vote() {
+        let cli = Cli::try_parse_from(["trello", "card", "vote", "abc123"]).unwrap();
         match cli.command {
             Commands::Card { command } => match command {
-                CardCommands::Delete { card_id } => {
+                CardCommands::Vote { card_id, remove } => {
                     assert_eq!(card_id, "abc123");
+                    assert!(!remove);
                 }
-                _ => panic!("Expected Delete command"),
+                _ => panic!("Expected Vote command"),
             },
             _ => panic!("Expected Card command"),
         }
-    }
 
-    #[test]
-    fn parse_list_move() {
-        let cli = Cli::try_parse_from(["trello", "list", "move", "list456", "bottom"]).unwrap();
+        let cli = Cli::try_parse_from(["trello", "card", "vote", "abc123", "--remove"]).unwrap();
         match cli.command {
-            Commands::List { command } => match command {
-                ListCommands::Move { list_id, position } => {
-                    assert_eq!(list_id, "list456");
-                    assert_eq!(position, "bottom");
-                }
-                _ => panic!("Expected Move command"),
+            Commands::Card { command } => match command {
+                CardCommands::Vote { remove, .. } => assert!(remove),
+                _ => panic!("Expected Vote command"),
             },
-            _ => panic!("Expected List command"),
+            _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_list_show() {
-        let cli = Cli::try_parse_from(["trello", "list", "show", "list456"]).unwrap();
+    fn test_list_siblings_orders_by_pos_and_marks_current() {
+        let cards = vec![
+            test_card("c", "Card c", 300.0),
+            test_card("a", "Card a", 100.0),
+            test_card("b", "Card b", 200.0),
+        ];
+        let siblings = list_siblings(&cards, "b");
+        let summary: Vec<(usize, &str, bool)> = siblings
+            .iter()
+            .map(|s| (s.ordinal, s.id.as_str(), s.current))
+            .collect();
+        assert_eq!(summary, [(1, "a", false), (2, "b", true), (3, "c", false)]);
+        assert_eq!(siblings[1].name, "Card b");
+    }
+
+    #[test]
+    fn parse_card_show_with_comments() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--comments"]).unwrap();
         match cli.command {
-            Commands::List { command } => match command {
-                ListCommands::Show { list_id, json } => {
-                    assert_eq!(list_id, "list456");
+            Commands::Card { command } => match command {
+                CardCommands::Show {
+                    card_ids,
+                    json,
+                    comments,
+                    ..
+                } => {
+                    assert_eq!(card_ids, vec!["abc123"]);
                     assert!(!json);
+                    assert!(comments);
                 }
                 _ => panic!("Expected Show command"),
             },
-            _ => panic!("Expected List command"),
+            _ => panic!("Expected Card command"),
         }
     }
 
     #[test]
-    fn parse_list_show_with_json() {
-        let cli = Cli::try_parse_from(["trello", "list", "show", "list456", "--json"]).unwrap();
+    fn parse_card_show_comment_filters() {
+        let cli = Cli::try_parse_from([
+            "trello",
+            "card",
+            "show",
+            "abc123",
+            "--comments",
+            "--since",
+            "2024-01-15",
+            "--last-N-comments",
+            "5",
+        ])
+        .unwrap();
         match cli.command {
-            Commands::List { command } => match command {
-                ListCommands::Show { list_id, json } => {
-                    assert_eq!(list_id, "list456");
-                    assert!(json);
+            Commands::Card { command } => match command {
+                CardCommands::Show {
+                    since,
+                    last_n_comments,
+                    ..
+                } => {
+                    assert_eq!(since, NaiveDate::from_ymd_opt(2024, 1, 15));
+                    assert_eq!(last_n_comments, Some(5));
                 }
                 _ => panic!("Expected Show command"),
             },
-            _ => panic!("Expected List command"),
+            _ => panic!("Expected Card command"),
         }
+
+        assert!(
+            Cli::try_parse_from([
+                "trello",
+                "card",
+                "show",
+                "abc",
+                "--comments",
+                "--since",
+                "1/15"
+            ])
+            .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["trello", "card", "show", "abc", "--last-N-comments", "3"])
+                .is_err()
+        );
+    }
+
+    fn test_comment(id: &str, date: &str) -> Action {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": "commentCard",
+            "date": date,
+            "data": { "text": "comment" },
+            "memberCreator": { "fullName": null, "username": "alice" }
+        }))
+        .unwrap()
     }
 
     #[test]
-    fn parse_board_show() {
-        let cli = Cli::try_parse_from(["trello", "board", "show", "board123"]).unwrap();
+    fn parse_watch() {
+        let cli = Cli::try_parse_from(["trello", "watch", "Work"]).unwrap();
         match cli.command {
-            Commands::Board { command } => match command {
-                BoardCommands::Show { board_id, json } => {
-                    assert_eq!(board_id, "board123");
-                    assert!(!json);
-                }
-            },
-            _ => panic!("Expected Board command"),
+            Commands::Watch {
+                board,
+                interval_secs,
+                json,
+            } => {
+                assert_eq!(board, "Work");
+                assert_eq!(interval_secs, 30);
+                assert!(!json);
+            }
+            _ => panic!("Expected Watch command"),
         }
-    }
 
-    #[test]
-    fn parse_board_show_with_json() {
-        let cli = Cli::try_parse_from(["trello", "board", "show", "board123", "--json"]).unwrap();
+        let cli =
+            Cli::try_parse_from(["trello", "watch", "Work", "--interval", "5", "--json"]).unwrap();
         match cli.command {
-            Commands::Board { command } => match command {
-                BoardCommands::Show { board_id, json } => {
-                    assert_eq!(board_id, "board123");
-                    assert!(json);
-                }
-            },
-            _ => panic!("Expected Board command"),
+            Commands::Watch {
+                interval_secs,
+                json,
+                ..
+            } => {
+                assert_eq!(interval_secs, 5);
+                assert!(json);
+            }
+            _ => panic!("Expected Watch command"),
         }
+
+        assert!(Cli::try_parse_from(["trello", "watch", "Work", "--interval", "0"]).is_err());
     }
 
     #[test]
-    fn parse_card_find_minimal() {
-        let cli = Cli::try_parse_from(["trello", "card", "find", "bug"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Find {
-                    pattern,
-                    board,
-                    list,
-                    json,
-                } => {
-                    assert_eq!(pattern, "bug");
-                    assert_eq!(board, None);
-                    assert_eq!(list, None);
-                    assert!(!json);
-                }
-                _ => panic!("Expected Find command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn test_take_new_actions() {
+        let mut cursor = Some("a1".to_string());
+        assert!(take_new_actions(Vec::new(), &mut cursor).is_empty());
+        assert_eq!(cursor.as_deref(), Some("a1"));
+
+        // The API returns newest first; they are printed oldest first
+        let actions = vec![
+            test_comment("a3", "2024-01-15T10:32:00.000Z"),
+            test_comment("a2", "2024-01-15T10:31:00.000Z"),
+        ];
+        let ids: Vec<String> = take_new_actions(actions, &mut cursor)
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        assert_eq!(ids, ["a2", "a3"]);
+        assert_eq!(cursor.as_deref(), Some("a3"));
+
+        let mut cursor = None;
+        take_new_actions(
+            vec![test_comment("a1", "2024-01-15T10:30:00.000Z")],
+            &mut cursor,
+        );
+        assert_eq!(cursor.as_deref(), Some("a1"));
     }
 
     #[test]
-    fn parse_card_find_with_board() {
-        let cli = Cli::try_parse_from(["trello", "card", "find", "task", "-b", "board"]).unwrap();
+    fn test_sleep_until_stopped_wakes_when_stopped() {
+        let stop = AtomicBool::new(true);
+        let start = std::time::Instant::now();
+        sleep_until_stopped(Duration::from_secs(60), &stop);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_format_action_line() {
+        let action = |data: serde_json::Value| -> Action {
+            serde_json::from_value(serde_json::json!({
+                "id": "a1",
+                "type": "updateCard",
+                "date": "2024-01-15T10:30:00.000Z",
+                "data": data,
+                "memberCreator": { "fullName": "Alice Smith", "username": "alice" }
+            }))
+            .unwrap()
+        };
+        let date = format_comment_date("2024-01-15T10:30:00.000Z");
+
+        let line = format_action_line(&action(serde_json::json!({
+            "card": { "id": "c1", "name": "Fix bug" },
+            "list": { "id": "l1", "name": "Doing" }
+        })));
+        assert_eq!(
+            line,
+            format!("[{}] Alice Smith: updateCard on card 'Fix bug'", date)
+        );
+
+        let line = format_action_line(&action(serde_json::json!({
+            "list": { "id": "l1", "name": "Doing" }
+        })));
+        assert_eq!(
+            line,
+            format!("[{}] Alice Smith: updateCard on list 'Doing'", date)
+        );
+
+        let line = format_action_line(&test_comment("a2", "2024-01-15T10:30:00.000Z"));
+        assert_eq!(line, format!("[{}] alice: commentCard", date));
+    }
+
+    #[test]
+    fn parse_board_activity() {
+        let cli = Cli::try_parse_from(["trello", "board", "activity", "Work"]).unwrap();
         match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Find {
-                    pattern,
-                    board,
-                    list,
-                    json,
-                } => {
-                    assert_eq!(pattern, "task");
-                    assert_eq!(board, Some("board".to_string()));
-                    assert_eq!(list, None);
-                    assert!(!json);
-                }
-                _ => panic!("Expected Find command"),
-            },
-            _ => panic!("Expected Card command"),
+            Commands::Board {
+                command:
+                    BoardCommands::Activity {
+                        board,
+                        limit,
+                        json,
+                        filter,
+                    },
+            } => {
+                assert_eq!(board, "Work");
+                assert_eq!(limit, 50);
+                assert!(!json);
+                assert_eq!(filter, None);
+            }
+            _ => panic!("Expected Board Activity command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "trello",
+            "board",
+            "activity",
+            "Work",
+            "--limit",
+            "10",
+            "--filter",
+            "createCard,commentCard",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Board {
+                command: BoardCommands::Activity { limit, filter, .. },
+            } => {
+                assert_eq!(limit, 10);
+                assert_eq!(filter.as_deref(), Some("createCard,commentCard"));
+            }
+            _ => panic!("Expected Board Activity command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["trello", "board", "activity", "Work", "--limit", "0"]).is_err()
+        );
+    }
+
+    /// Canned responses for the `config validate` API checks.
+    struct FakeAccountApi {
+        member: Option<Member>,
+        boards: Option<usize>,
+    }
+
+    impl AccountApi for FakeAccountApi {
+        fn current_member(&self) -> Result<Member> {
+            self.member
+                .clone()
+                .ok_or_else(|| TrelloError::Auth("invalid token".to_string()).into())
+        }
+
+        fn member_boards(&self) -> Result<Vec<Board>> {
+            let count = self.boards.context("connection reset")?;
+            Ok((0..count)
+                .map(|i| Board {
+                    id: i.to_string(),
+                    name: format!("Board {}", i),
+                    date_last_activity: None,
+                    short_url: None,
+                    closed: false,
+                    id_organization: None,
+                })
+                .collect())
         }
     }
 
+    fn validation_messages(checks: &[Check]) -> Vec<String> {
+        checks
+            .iter()
+            .map(|c| format!("{} {}", if c.ok { "OK" } else { "FAIL" }, c.message))
+            .collect()
+    }
+
+    #[test]
+    fn validate_setup_passes_every_check() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "api_key = \"key\"\napi_token = \"token\"\n").unwrap();
+        let alice = Member {
+            id: "m1".to_string(),
+            username: "alice".to_string(),
+            full_name: Some("Alice Smith".to_string()),
+        };
+
+        let checks = validate_setup(&path, &HashMap::new(), |config| {
+            assert_eq!(config.api_key(), "key");
+            Ok(FakeAccountApi {
+                member: Some(alice),
+                boards: Some(3),
+            })
+        });
+        assert_eq!(
+            validation_messages(&checks),
+            [
+                format!("OK Config file {} is valid", path.display()),
+                "OK API key and token are set (config file)".to_string(),
+                "OK Authenticated as Alice Smith (@alice)".to_string(),
+                "OK 3 accessible boards".to_string(),
+            ]
+        );
+    }
+
     #[test]
-    fn parse_card_find_with_list() {
-        let cli = Cli::try_parse_from(["trello", "card", "find", "urgent", "-l", "list"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Find {
-                    pattern,
-                    board,
-                    list,
-                    json,
-                } => {
-                    assert_eq!(pattern, "urgent");
-                    assert_eq!(board, None);
-                    assert_eq!(list, Some("list".to_string()));
-                    assert!(!json);
-                }
-                _ => panic!("Expected Find command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn validate_setup_reports_failures() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        let env: HashMap<String, String> = [
+            ("TRELLO_API_KEY".to_string(), "key".to_string()),
+            ("TRELLO_API_TOKEN".to_string(), "token".to_string()),
+        ]
+        .into();
+
+        // Rejected credentials: the boards check is skipped
+        let checks = validate_setup(&path, &env, |_| {
+            Ok(FakeAccountApi {
+                member: None,
+                boards: Some(3),
+            })
+        });
+        let messages = validation_messages(&checks);
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].starts_with("OK No config file"));
+        assert_eq!(
+            messages[1],
+            "OK API key and token are set (environment variables)"
+        );
+        assert_eq!(messages[2], "FAIL Failed to authenticate: invalid token");
+
+        // Boards can't be fetched
+        let member = Member {
+            id: "m1".to_string(),
+            username: "alice".to_string(),
+            full_name: None,
+        };
+        let checks = validate_setup(&path, &env, |_| {
+            Ok(FakeAccountApi {
+                member: Some(member),
+                boards: None,
+            })
+        });
+        let messages = validation_messages(&checks);
+        assert_eq!(messages[2], "OK Authenticated as alice (@alice)");
+        assert_eq!(messages[3], "FAIL Failed to fetch boards: connection reset");
+
+        // No credentials anywhere: nothing is requested
+        let checks = validate_setup(&path, &HashMap::new(), |_| -> Result<FakeAccountApi> {
+            panic!("should not connect without credentials")
+        });
+        let messages = validation_messages(&checks);
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with("FAIL Config file"));
+        assert!(messages[1].starts_with("FAIL Failed to load Trello credentials"));
+
+        // A malformed config file
+        fs::write(&path, "api_key = ").unwrap();
+        let checks = validate_setup(&path, &HashMap::new(), |_| -> Result<FakeAccountApi> {
+            panic!("should not connect without credentials")
+        });
+        assert!(validation_messages(&checks)[0].starts_with("FAIL Failed to parse config file"));
     }
 
     #[test]
-    fn parse_card_find_with_json() {
-        let cli = Cli::try_parse_from(["trello", "card", "find", "test", "--json"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Find {
-                    pattern,
-                    board,
-                    list,
-                    json,
-                } => {
-                    assert_eq!(pattern, "test");
-                    assert_eq!(board, None);
-                    assert_eq!(list, None);
-                    assert!(json);
-                }
-                _ => panic!("Expected Find command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+    fn format_reply_mentions_comment_author() {
+        let action = test_comment("a", "2024-01-10T09:00:00.000Z");
+        assert_eq!(
+            format_reply(&action, "Agreed, let's ship it").unwrap(),
+            "@alice Agreed, let's ship it"
+        );
+
+        let action = Action {
+            action_type: "updateCard".to_string(),
+            ..test_comment("b", "2024-01-10T09:00:00.000Z")
+        };
+        let err = format_reply(&action, "hi").unwrap_err();
+        assert_eq!(error::exit_code(&err), ExitCode::UserError);
     }
 
     #[test]
-    fn parse_card_find_full() {
+    fn parse_card_comment_reply() {
         let cli = Cli::try_parse_from([
             "trello",
             "card",
-            "find",
-            "fix",
-            "-b",
-            "project",
-            "-l",
-            "in-progress",
-            "--json",
+            "comment-reply",
+            "abc123",
+            "5f0c1d2e3a4b5c6d7e8f9a0b",
+            "Thanks!",
         ])
         .unwrap();
         match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Find {
-                    pattern,
-                    board,
-                    list,
-                    json,
-                } => {
-                    assert_eq!(pattern, "fix");
-                    assert_eq!(board, Some("project".to_string()));
-                    assert_eq!(list, Some("in-progress".to_string()));
-                    assert!(json);
-                }
-                _ => panic!("Expected Find command"),
-            },
-            _ => panic!("Expected Card command"),
+            Commands::Card {
+                command:
+                    CardCommands::CommentReply {
+                        card_id,
+                        action_id,
+                        text,
+                    },
+            } => {
+                assert_eq!(card_id, "abc123");
+                assert_eq!(action_id, "5f0c1d2e3a4b5c6d7e8f9a0b");
+                assert_eq!(text, "Thanks!");
+            }
+            _ => panic!("Expected Card CommentReply command"),
         }
     }
 
     #[test]
-    fn parse_card_show() {
-        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Show {
-                    card_id,
-                    json,
-                    comments,
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(!json);
-                    assert!(!comments);
-                }
-                _ => panic!("Expected Show command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
-    }
+    fn filter_comments_applies_since_then_last_n() {
+        let comments = || {
+            vec![
+                test_comment("a", "2024-01-10T09:00:00.000Z"),
+                test_comment("b", "2024-01-15T00:00:00.000Z"),
+                test_comment("c", "2024-01-16T12:00:00.000Z"),
+                test_comment("d", "2024-01-20T18:30:00.000Z"),
+            ]
+        };
+        let ids = |actions: Vec<Action>| actions.into_iter().map(|a| a.id).collect::<Vec<_>>();
+        let since = NaiveDate::from_ymd_opt(2024, 1, 15);
 
-    #[test]
-    fn parse_card_show_with_json() {
-        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--json"]).unwrap();
-        match cli.command {
-            Commands::Card { command } => match command {
-                CardCommands::Show {
-                    card_id,
-                    json,
-                    comments,
-                } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(json);
-                    assert!(!comments);
-                }
-                _ => panic!("Expected Show command"),
-            },
-            _ => panic!("Expected Card command"),
-        }
+        assert_eq!(
+            ids(filter_comments(comments(), None, None)),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!(
+            ids(filter_comments(comments(), since, None)),
+            ["b", "c", "d"]
+        );
+        assert_eq!(ids(filter_comments(comments(), None, Some(2))), ["c", "d"]);
+        assert_eq!(
+            ids(filter_comments(comments(), since, Some(10))),
+            ["b", "c", "d"]
+        );
+        assert_eq!(ids(filter_comments(comments(), since, Some(1))), ["d"]);
+        assert!(filter_comments(comments(), None, Some(0)).is_empty());
     }
 
     #[test]
-    fn parse_card_show_with_comments() {
-        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--comments"]).unwrap();
+    fn parse_card_show_with_json_and_comments() {
+        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--json", "--comments"])
+            .unwrap();
         match cli.command {
             Commands::Card { command } => match command {
                 CardCommands::Show {
-                    card_id,
+                    card_ids,
                     json,
                     comments,
+                    ..
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(!json);
+                    assert_eq!(card_ids, vec!["abc123"]);
+                    assert!(json);
                     assert!(comments);
                 }
                 _ => panic!("Expected Show command"),
@@ -1352,19 +9786,16 @@ mod tests {
     }
 
     #[test]
-    fn parse_card_show_with_json_and_comments() {
-        let cli = Cli::try_parse_from(["trello", "card", "show", "abc123", "--json", "--comments"])
-            .unwrap();
+    fn parse_card_show_by_name_with_board() {
+        let cli =
+            Cli::try_parse_from(["trello", "card", "show", "login bug", "-b", "Work"]).unwrap();
         match cli.command {
             Commands::Card { command } => match command {
                 CardCommands::Show {
-                    card_id,
-                    json,
-                    comments,
+                    card_ids, board, ..
                 } => {
-                    assert_eq!(card_id, "abc123");
-                    assert!(json);
-                    assert!(comments);
+                    assert_eq!(card_ids, vec!["login bug"]);
+                    assert_eq!(board, Some("Work".to_string()));
                 }
                 _ => panic!("Expected Show command"),
             },
@@ -1384,6 +9815,72 @@ mod tests {
         );
         // Short string returns as-is
         assert_eq!(format_comment_date("short"), "short");
+        // dateLastActivity timestamps use the same format
+        assert_eq!(
+            format_comment_date("2024-01-15T10:30:59.999Z"),
+            "2024-01-15 10:30"
+        );
+    }
+
+    #[test]
+    fn test_parse_card_show_render_markdown_conflicts_with_raw() {
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "show",
+            "abc123",
+            "--render-markdown",
+            "--raw-description",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_qr_to_string() {
+        let qr = render_qr_to_string("https://trello.com/c/AbCd1234").unwrap();
+        let lines: Vec<&str> = qr.lines().collect();
+        let width = lines[0].chars().count();
+        assert!(width <= 40, "QR code is {} columns wide", width);
+        assert!(lines.iter().all(|l| l.chars().count() == width));
+        // Two module rows per line
+        assert_eq!(lines.len(), width.div_ceil(2));
+        // The quiet zone is drawn solid on the first line
+        assert!(lines[0].chars().all(|c| c == '█'));
+        assert!(
+            qr.chars()
+                .all(|c| matches!(c, '█' | '▀' | '▄' | ' ' | '\n'))
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_inline_styles() {
+        assert_eq!(
+            render_markdown_to_terminal("Some **bold** and *italic* text", 80),
+            "Some \x1b[1mbold\x1b[0m and \x1b[3mitalic\x1b[0m text\n"
+        );
+        // Unmatched markers are left alone
+        assert_eq!(render_markdown_to_terminal("2 * 3", 80), "2 * 3\n");
+    }
+
+    #[test]
+    fn test_render_markdown_headers_and_lists() {
+        let rendered = render_markdown_to_terminal("# Title\n\n- one\n- two", 80);
+        assert_eq!(rendered, "\x1b[1mTitle\x1b[0m\n\n  • one\n  • two\n");
+    }
+
+    #[test]
+    fn test_render_markdown_code_block() {
+        let rendered = render_markdown_to_terminal("```\nlet *x* = 1;\n```", 80);
+        assert_eq!(rendered, "    \x1b[2mlet *x* = 1;\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_render_markdown_wraps_to_width() {
+        let rendered = render_markdown_to_terminal("one two three four", 9);
+        assert_eq!(rendered, "one two\nthree\nfour\n");
+
+        let rendered = render_markdown_to_terminal("- alpha beta gamma", 12);
+        assert_eq!(rendered, "  • alpha\n    beta\n    gamma\n");
     }
 
     #[test]
@@ -1415,8 +9912,19 @@ mod tests {
                 },
             ],
             description: "The login page times out".to_string(),
+            start: None,
             archived: false,
+            cover_color: Some("blue".to_string()),
+            vote_count: Some(2),
+            has_voted: Some(true),
+            due: Some("2024-02-01T12:00:00.000Z".to_string()),
+            last_activity: Some("2024-01-15 10:30".to_string()),
+            url: "https://trello.com/c/abc/1-fix-login-bug".to_string(),
+            position: None,
             comments: None,
+            siblings: None,
+            webhooks: None,
+            short_url: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1432,8 +9940,17 @@ mod tests {
         assert_eq!(parsed["labels"][1]["color"], serde_json::Value::Null);
         assert_eq!(parsed["description"], "The login page times out");
         assert_eq!(parsed["archived"], false);
+        assert_eq!(parsed["cover_color"], "blue");
+        assert_eq!(parsed["vote_count"], 2);
+        assert_eq!(parsed["has_voted"], true);
+        assert_eq!(parsed["due"], "2024-02-01T12:00:00.000Z");
+        assert_eq!(parsed["last_activity"], "2024-01-15 10:30");
+        assert_eq!(parsed["url"], "https://trello.com/c/abc/1-fix-login-bug");
+        assert!(!parsed.as_object().unwrap().contains_key("position"));
         // Comments field should not be present when None
         assert!(!parsed.as_object().unwrap().contains_key("comments"));
+        // Siblings are only emitted with --board-context
+        assert!(!parsed.as_object().unwrap().contains_key("siblings"));
     }
 
     #[test]
@@ -1445,19 +9962,38 @@ mod tests {
             list: "In Progress".to_string(),
             labels: vec![],
             description: "".to_string(),
+            start: None,
             archived: true,
+            cover_color: None,
+            vote_count: None,
+            has_voted: None,
+            due: None,
+            last_activity: None,
+            url: String::new(),
+            position: Some(PositionInfo {
+                ordinal: 3,
+                total: 7,
+                raw: 98304.0,
+            }),
             comments: Some(vec![
                 CommentInfo {
+                    id: "c1".to_string(),
                     date: "2024-01-15 10:30".to_string(),
                     author: "Alice".to_string(),
                     text: "I can reproduce this".to_string(),
+                    highlight_match: None,
                 },
                 CommentInfo {
+                    id: "c2".to_string(),
                     date: "2024-01-15 14:45".to_string(),
                     author: "Bob".to_string(),
                     text: "Fixed in commit abc123".to_string(),
+                    highlight_match: None,
                 },
             ]),
+            siblings: None,
+            webhooks: None,
+            short_url: None,
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -1465,6 +10001,9 @@ mod tests {
 
         assert_eq!(parsed["id"], "507f1f77bcf86cd799439011");
         assert_eq!(parsed["archived"], true);
+        assert_eq!(parsed["position"]["ordinal"], 3);
+        assert_eq!(parsed["position"]["total"], 7);
+        assert_eq!(parsed["position"]["raw"], 98304.0);
         assert_eq!(parsed["description"], "");
         assert!(parsed["labels"].as_array().unwrap().is_empty());
         assert_eq!(parsed["comments"][0]["date"], "2024-01-15 10:30");
@@ -1475,6 +10014,168 @@ mod tests {
         assert_eq!(parsed["comments"][1]["text"], "Fixed in commit abc123");
     }
 
+    #[test]
+    fn test_show_card_result_webhooks_serialization() {
+        let parsed = serde_json::to_value(template_result()).unwrap();
+        assert!(parsed.get("webhooks").is_none());
+        assert!(parsed.get("short_url").is_none());
+
+        let result = ShowCardResult {
+            webhooks: Some(vec![Webhook {
+                id: "w1".to_string(),
+                description: "CI sync".to_string(),
+                callback_url: "https://example.com/hook".to_string(),
+                id_model: "507f1f77bcf86cd799439011".to_string(),
+                active: false,
+            }]),
+            ..template_result()
+        };
+        let parsed = serde_json::to_value(&result).unwrap();
+        assert_eq!(parsed["webhooks"][0]["id"], "w1");
+        assert_eq!(
+            parsed["webhooks"][0]["callbackURL"],
+            "https://example.com/hook"
+        );
+        assert_eq!(parsed["webhooks"][0]["active"], false);
+    }
+
+    fn template_result() -> ShowCardResult {
+        ShowCardResult {
+            id: "507f1f77bcf86cd799439011".to_string(),
+            name: "Fix login bug".to_string(),
+            board: "Project Alpha".to_string(),
+            list: "In Progress".to_string(),
+            labels: vec![
+                LabelInfo {
+                    name: "Bug".to_string(),
+                    color: Some("red".to_string()),
+                },
+                LabelInfo {
+                    name: "Urgent".to_string(),
+                    color: None,
+                },
+            ],
+            description: "Times out".to_string(),
+            start: None,
+            archived: false,
+            cover_color: None,
+            vote_count: None,
+            has_voted: None,
+            due: Some("2024-02-01T12:00:00.000Z".to_string()),
+            last_activity: None,
+            url: "https://trello.com/c/abc".to_string(),
+            position: None,
+            comments: None,
+            siblings: None,
+            webhooks: None,
+            short_url: None,
+        }
+    }
+
+    #[test]
+    fn test_select_fields() {
+        let result = template_result();
+        let requested = ["board", "name", "bogus", "board"].map(String::from);
+        let fields = known_show_fields(&requested);
+        assert_eq!(fields, ["board", "name"]);
+
+        let selected = select_fields(&result, &fields).unwrap();
+        assert_eq!(
+            selected,
+            [
+                ("board".to_string(), serde_json::json!("Project Alpha")),
+                ("name".to_string(), serde_json::json!("Fix login bug")),
+            ]
+        );
+
+        let selected = select_fields(&result, &["labels", "archived", "due"]).unwrap();
+        let text: Vec<String> = selected.iter().map(|(_, v)| field_text(v)).collect();
+        assert_eq!(text, ["Bug, Urgent", "false", "2024-02-01T12:00:00.000Z"]);
+
+        let result = ShowCardResult {
+            due: None,
+            ..template_result()
+        };
+        let selected = select_fields(&result, &["due"]).unwrap();
+        assert_eq!(selected[0].1, serde_json::Value::Null);
+        assert_eq!(field_text(&selected[0].1), "");
+    }
+
+    #[test]
+    fn parse_card_show_field_conflicts_with_output_format() {
+        let cli = Cli::try_parse_from([
+            "trello", "card", "show", "abc", "--field", "name", "--field", "board",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Card {
+                command: CardCommands::Show { field, .. },
+            } => assert_eq!(field, ["name", "board"]),
+            _ => panic!("Expected Card Show command"),
+        }
+
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "show",
+            "abc",
+            "--field",
+            "name",
+            "--output-format",
+            "{name}",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_card_template_all_placeholders() {
+        let result = template_result();
+        assert_eq!(
+            render_card_template("{id}|{name}|{board}|{list}", &result),
+            "507f1f77bcf86cd799439011|Fix login bug|Project Alpha|In Progress"
+        );
+        assert_eq!(
+            render_card_template("{description}|{labels}|{archived}", &result),
+            "Times out|Bug, Urgent|false"
+        );
+        assert_eq!(
+            render_card_template("{due} {url}", &result),
+            "2024-02-01T12:00:00.000Z https://trello.com/c/abc"
+        );
+    }
+
+    #[test]
+    fn test_render_card_template_combinations() {
+        let mut result = template_result();
+        assert_eq!(
+            render_card_template("{name} [{list}]", &result),
+            "Fix login bug [In Progress]"
+        );
+        // Repeated and unknown placeholders
+        assert_eq!(
+            render_card_template("{id}{id} {unknown} {", &result),
+            "507f1f77bcf86cd799439011507f1f77bcf86cd799439011 {unknown} {"
+        );
+        // Missing due date renders empty; values are not re-expanded
+        result.due = None;
+        result.name = "{list}".to_string();
+        assert_eq!(render_card_template("{name}:{due}", &result), "{list}:");
+    }
+
+    #[test]
+    fn parse_card_show_output_format_conflicts_with_json() {
+        let result = Cli::try_parse_from([
+            "trello",
+            "card",
+            "show",
+            "abc123",
+            "--json",
+            "--output-format",
+            "{name}",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_label_info_serialization() {
         let label_with_color = LabelInfo {
@@ -1497,16 +10198,58 @@ mod tests {
     #[test]
     fn test_comment_info_serialization() {
         let comment = CommentInfo {
+            id: "c1".to_string(),
             date: "2024-01-15 10:30".to_string(),
             author: "Alice".to_string(),
             text: "Test comment".to_string(),
+            highlight_match: None,
         };
         let json = serde_json::to_string(&comment).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
+        assert_eq!(parsed["id"], "c1");
         assert_eq!(parsed["date"], "2024-01-15 10:30");
         assert_eq!(parsed["author"], "Alice");
         assert_eq!(parsed["text"], "Test comment");
+        assert!(parsed.get("highlight_match").is_none());
+
+        let comment = CommentInfo {
+            highlight_match: Some(true),
+            ..comment
+        };
+        let parsed = serde_json::to_value(&comment).unwrap();
+        assert_eq!(parsed["highlight_match"], true);
+    }
+
+    #[test]
+    fn test_format_comment_line_highlighting() {
+        let comment = |highlight_match| CommentInfo {
+            id: "c1".to_string(),
+            date: "2024-01-15 10:30".to_string(),
+            author: "Alice".to_string(),
+            text: "See PR #42".to_string(),
+            highlight_match,
+        };
+        assert_eq!(
+            format_comment_line(&comment(None), true),
+            "  [2024-01-15 10:30] Alice: See PR #42"
+        );
+        assert_eq!(
+            format_comment_line(&comment(Some(true)), false),
+            ">>> [2024-01-15 10:30] Alice: See PR #42"
+        );
+        assert_eq!(
+            format_comment_line(&comment(Some(false)), false),
+            "    [2024-01-15 10:30] Alice: See PR #42"
+        );
+        assert_eq!(
+            format_comment_line(&comment(Some(true)), true),
+            ">>> \x1b[1m[2024-01-15 10:30] Alice: See PR #42\x1b[0m"
+        );
+        assert_eq!(
+            format_comment_line(&comment(Some(false)), true),
+            "    \x1b[2m[2024-01-15 10:30] Alice: See PR #42\x1b[0m"
+        );
     }
 
     #[test]
@@ -7,6 +7,93 @@ use serde::{Deserialize, Serialize};
 pub struct Board {
     pub id: String,
     pub name: String,
+    #[serde(rename = "dateLastActivity", default)]
+    pub date_last_activity: Option<String>, // ISO 8601 timestamp
+    #[serde(rename = "shortUrl", default)]
+    pub short_url: Option<String>,
+    #[serde(default)]
+    pub closed: bool,
+    /// The workspace the board belongs to, if any
+    #[serde(rename = "idOrganization", default)]
+    pub id_organization: Option<String>,
+}
+
+/// A board together with its open lists, open cards and members,
+/// fetched as nested resources in a single request
+#[derive(Debug, Deserialize)]
+pub struct BoardWithDetails {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "shortUrl", default)]
+    pub short_url: Option<String>,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub lists: Vec<List>,
+    #[serde(default)]
+    pub cards: Vec<CardRef>,
+    #[serde(default)]
+    pub members: Vec<BoardMember>,
+}
+
+/// Minimal card reference: just enough to count cards per list
+#[derive(Debug, Deserialize)]
+pub struct CardRef {
+    pub id: String,
+    #[serde(rename = "idList")]
+    pub id_list: String,
+}
+
+/// Represents a Trello member
+#[derive(Debug, Deserialize, Clone)]
+pub struct Member {
+    pub id: String,
+    pub username: String,
+    #[serde(rename = "fullName")]
+    pub full_name: Option<String>,
+}
+
+/// Represents a Trello workspace (organization)
+#[derive(Debug, Deserialize, Clone)]
+pub struct Organization {
+    pub id: String,
+    /// The workspace's short name, as used in its URL
+    pub name: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+}
+
+/// Represents a webhook registered with the API token
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Webhook {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(rename = "callbackURL")]
+    pub callback_url: String,
+    /// The ID of the board, list, card or member the webhook watches
+    #[serde(rename = "idModel")]
+    pub id_model: String,
+    pub active: bool,
+}
+
+/// Represents a member of a Trello board, including their role on the board
+#[derive(Debug, Deserialize, Clone)]
+pub struct BoardMember {
+    pub id: String,
+    pub username: String,
+    #[serde(rename = "fullName")]
+    pub full_name: Option<String>,
+    #[serde(rename = "memberType", default)]
+    pub member_type: String,
+}
+
+/// Request body for inviting a member to a board by email
+#[derive(Debug, Serialize)]
+pub struct InviteMember {
+    pub email: String,
+    #[serde(rename = "type")]
+    pub member_type: String,
 }
 
 /// Represents a Trello card
@@ -25,6 +112,68 @@ pub struct Card {
     #[serde(default)]
     pub closed: bool,
     pub pos: f64,
+    #[serde(default)]
+    pub cover: Option<CardCover>,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub due: Option<String>,
+    #[serde(default)]
+    pub url: String,
+    #[serde(rename = "dateLastActivity", default)]
+    pub date_last_activity: Option<String>,
+    #[serde(rename = "shortUrl", default)]
+    pub short_url: Option<String>,
+    #[serde(rename = "shortLink", default)]
+    pub short_link: Option<String>,
+    #[serde(default)]
+    pub badges: Option<CardBadges>,
+    #[serde(rename = "idMembersVoted", default)]
+    pub id_members_voted: Vec<String>,
+    #[serde(rename = "idMembers", default)]
+    pub id_members: Vec<String>,
+}
+
+/// Summary counts Trello attaches to a card
+#[derive(Debug, Deserialize, Clone)]
+pub struct CardBadges {
+    #[serde(default)]
+    pub votes: u32,
+    /// Total items across all the card's checklists
+    #[serde(rename = "checkItems", default)]
+    pub check_items: u32,
+    #[serde(rename = "checkItemsChecked", default)]
+    pub check_items_checked: u32,
+}
+
+/// Request body for voting on a card
+#[derive(Debug, Serialize)]
+pub struct VoteOnCard {
+    pub value: String,
+}
+
+/// Trello card cover settings
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardCover {
+    pub color: Option<String>,
+}
+
+/// Request body for setting or clearing a card's cover
+#[derive(Debug, Serialize)]
+pub struct UpdateCardCover {
+    pub cover: CardCover,
+}
+
+/// Request body for setting or clearing a card's start date
+#[derive(Debug, Serialize)]
+pub struct UpdateCardStart {
+    pub start: Option<String>,
+}
+
+/// Request body for renaming a card
+#[derive(Debug, Serialize)]
+pub struct UpdateCardName {
+    pub name: String,
 }
 
 /// Request body for updating a card's description
@@ -48,12 +197,30 @@ pub struct AddLabel {
     pub value: String,
 }
 
+/// Request body for assigning a member to a card
+#[derive(Debug, Serialize)]
+pub struct AddMember {
+    pub value: String,
+}
+
 /// Request body for archiving a card
 #[derive(Debug, Serialize)]
 pub struct ArchiveCard {
     pub closed: bool,
 }
 
+/// Request body for closing or reopening a board
+#[derive(Debug, Serialize)]
+pub struct UpdateBoardClosed {
+    pub value: bool,
+}
+
+/// Request body for archiving or restoring a list
+#[derive(Debug, Serialize)]
+pub struct ArchiveList {
+    pub closed: bool,
+}
+
 /// Request body for adding a comment to a card
 #[derive(Debug, Serialize)]
 pub struct AddComment {
@@ -66,6 +233,16 @@ pub struct UpdateCardPosition {
     pub pos: String,
 }
 
+/// Request body for moving a card to another list, possibly on another board
+#[derive(Debug, Serialize)]
+pub struct MoveCardToList {
+    #[serde(rename = "idList")]
+    pub id_list: String,
+    #[serde(rename = "idBoard")]
+    pub id_board: String,
+    pub pos: String,
+}
+
 /// Request body for creating a card
 #[derive(Debug, Serialize)]
 pub struct CreateCard {
@@ -75,6 +252,10 @@ pub struct CreateCard {
     pub id_list: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub desc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>, // ISO 8601 timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>, // ISO 8601 timestamp
 }
 
 /// Represents a Trello list
@@ -85,6 +266,29 @@ pub struct List {
     #[serde(rename = "idBoard")]
     pub id_board: String,
     pub pos: f64,
+    #[serde(default)]
+    pub closed: bool,
+}
+
+/// Request body for creating a list on a board
+#[derive(Debug, Serialize)]
+pub struct CreateList {
+    pub name: String,
+    #[serde(rename = "idBoard")]
+    pub id_board: String,
+    pub pos: String,
+}
+
+/// Request body for copying an existing card into a list
+#[derive(Debug, Serialize)]
+pub struct CopyCard {
+    #[serde(rename = "idList")]
+    pub id_list: String,
+    #[serde(rename = "idCardSource")]
+    pub id_card_source: String,
+    #[serde(rename = "keepFromSource")]
+    pub keep_from_source: String,
+    pub pos: String,
 }
 
 /// Request body for updating a list's position
@@ -93,8 +297,44 @@ pub struct UpdateListPosition {
     pub pos: String,
 }
 
-/// Represents a Trello action (used for comments)
+/// Just the ID of a Trello action, for use as a `since` cursor
 #[derive(Debug, Deserialize)]
+pub struct ActionRef {
+    pub id: String,
+}
+
+/// A checklist on a card, with its items
+#[derive(Debug, Deserialize, Clone)]
+pub struct Checklist {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "checkItems", default)]
+    pub check_items: Vec<CheckItem>,
+}
+
+/// An item within a checklist
+#[derive(Debug, Deserialize, Clone)]
+pub struct CheckItem {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub state: String, // "complete" or "incomplete"
+}
+
+/// Request body for creating a checklist on a card
+#[derive(Debug, Serialize)]
+pub struct CreateChecklist {
+    pub name: String,
+}
+
+/// Request body for adding an item to a checklist
+#[derive(Debug, Serialize)]
+pub struct AddCheckItem {
+    pub name: String,
+}
+
+/// Represents a Trello action (a comment, or any other change on a board)
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Action {
     pub id: String,
     #[serde(rename = "type")]
@@ -105,13 +345,33 @@ pub struct Action {
     pub member_creator: ActionMember, // Always present for commentCard actions
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ActionData {
     #[serde(default)]
     pub text: String, // Comment text; empty string if not present
+    /// The card the action affected, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card: Option<ActionCardRef>,
+    /// The list the action affected, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list: Option<ActionListRef>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ActionCardRef {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ActionListRef {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ActionMember {
     #[serde(rename = "fullName")]
     pub full_name: Option<String>,
@@ -120,7 +380,115 @@ pub struct ActionMember {
 
 #[cfg(test)]
 mod tests {
-    use super::CreateCard;
+    use super::{
+        Action, Board, BoardWithDetails, Card, CardCover, Checklist, CopyCard, CreateCard,
+        InviteMember, UpdateBoardClosed, UpdateCardCover, UpdateCardStart, Webhook,
+    };
+
+    #[test]
+    fn update_card_start_serializes_null_when_clearing() {
+        let body = UpdateCardStart {
+            start: Some("2024-03-01T05:00:00.000Z".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_string(&body).unwrap(),
+            r#"{"start":"2024-03-01T05:00:00.000Z"}"#
+        );
+
+        let body = UpdateCardStart { start: None };
+        assert_eq!(serde_json::to_string(&body).unwrap(), r#"{"start":null}"#);
+    }
+
+    #[test]
+    fn card_deserializes_short_url() {
+        let card: Card = serde_json::from_str(
+            r#"{"id":"1","name":"Card","idBoard":"b","idList":"l","pos":1.0,
+                "shortUrl":"https://trello.com/c/abc123","shortLink":"abc123"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            card.short_url.as_deref(),
+            Some("https://trello.com/c/abc123")
+        );
+        assert_eq!(card.short_link.as_deref(), Some("abc123"));
+
+        let card: Card = serde_json::from_str(
+            r#"{"id":"1","name":"Card","idBoard":"b","idList":"l","pos":1.0}"#,
+        )
+        .unwrap();
+        assert_eq!(card.short_url, None);
+    }
+
+    #[test]
+    fn board_deserializes_short_url_and_closed() {
+        let board: Board = serde_json::from_str(
+            r#"{"id":"b1","name":"Work","shortUrl":"https://trello.com/b/xyz789","closed":true}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            board.short_url.as_deref(),
+            Some("https://trello.com/b/xyz789")
+        );
+        assert!(board.closed);
+
+        let board: Board = serde_json::from_str(r#"{"id":"b1","name":"Work"}"#).unwrap();
+        assert_eq!(board.short_url, None);
+        assert!(!board.closed);
+    }
+
+    #[test]
+    fn board_with_details_deserializes_nested_resources() {
+        let board: BoardWithDetails = serde_json::from_str(
+            r#"{"id":"b1","name":"Work","shortUrl":"https://trello.com/b/xyz789","closed":false,
+                "lists":[{"id":"l1","name":"Todo","idBoard":"b1","pos":1.0}],
+                "cards":[{"id":"c1","idList":"l1"},{"id":"c2","idList":"l1"}],
+                "members":[{"id":"m1","username":"alice","fullName":"Alice"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(board.lists[0].name, "Todo");
+        assert_eq!(board.cards.len(), 2);
+        assert_eq!(board.cards[1].id_list, "l1");
+        assert_eq!(board.members[0].username, "alice");
+    }
+
+    #[test]
+    fn checklist_deserializes_items() {
+        let checklists: Vec<Checklist> = serde_json::from_str(
+            r#"[{"id":"c1","name":"Release","checkItems":[
+                {"id":"i1","name":"Tag build","state":"complete"},
+                {"id":"i2","name":"Write notes","state":"incomplete"}]}]"#,
+        )
+        .unwrap();
+        assert_eq!(checklists[0].name, "Release");
+        assert_eq!(checklists[0].check_items.len(), 2);
+        assert_eq!(checklists[0].check_items[1].name, "Write notes");
+        assert_eq!(checklists[0].check_items[1].state, "incomplete");
+    }
+
+    #[test]
+    fn card_deserializes_votes() {
+        let card: Card = serde_json::from_str(
+            r#"{"id":"1","name":"Card","idBoard":"b","idList":"l","pos":1.0,
+                "badges":{"votes":3,"comments":2},"idMembersVoted":["m1","m2","m3"]}"#,
+        )
+        .unwrap();
+        assert_eq!(card.badges.as_ref().map(|b| b.votes), Some(3));
+        assert_eq!(card.badges.as_ref().map(|b| b.check_items), Some(0));
+        assert_eq!(card.id_members_voted, ["m1", "m2", "m3"]);
+        assert!(card.id_members.is_empty());
+    }
+
+    #[test]
+    fn card_deserializes_checklist_badges() {
+        let card: Card = serde_json::from_str(
+            r#"{"id":"1","name":"Card","idBoard":"b","idList":"l","pos":1.0,
+                "badges":{"votes":0,"checkItems":5,"checkItemsChecked":2}}"#,
+        )
+        .unwrap();
+        let badges = card.badges.unwrap();
+        assert_eq!(badges.check_items, 5);
+        assert_eq!(badges.check_items_checked, 2);
+    }
 
     #[test]
     fn create_card_serializes_with_description() {
@@ -129,6 +497,8 @@ mod tests {
             pos: "top".to_string(),
             id_list: "507f1f77bcf86cd799439011".to_string(),
             desc: Some("Some description".to_string()),
+            due: Some("2024-03-01T08:00:00.000Z".to_string()),
+            start: None,
         };
 
         let value = serde_json::to_value(body).unwrap();
@@ -136,6 +506,8 @@ mod tests {
         assert_eq!(value["pos"], "top");
         assert_eq!(value["idList"], "507f1f77bcf86cd799439011");
         assert_eq!(value["desc"], "Some description");
+        assert_eq!(value["due"], "2024-03-01T08:00:00.000Z");
+        assert!(value.get("start").is_none());
     }
 
     #[test]
@@ -145,6 +517,8 @@ mod tests {
             pos: "bottom".to_string(),
             id_list: "507f1f77bcf86cd799439011".to_string(),
             desc: None,
+            due: None,
+            start: None,
         };
 
         let value = serde_json::to_value(body).unwrap();
@@ -152,5 +526,107 @@ mod tests {
         assert_eq!(value["pos"], "bottom");
         assert_eq!(value["idList"], "507f1f77bcf86cd799439011");
         assert!(value.get("desc").is_none());
+        assert!(value.get("due").is_none());
+        assert!(value.get("start").is_none());
+    }
+
+    #[test]
+    fn copy_card_serializes_source_fields() {
+        let body = CopyCard {
+            id_list: "l1".to_string(),
+            id_card_source: "c1".to_string(),
+            keep_from_source: "all".to_string(),
+            pos: "bottom".to_string(),
+        };
+        let value = serde_json::to_value(body).unwrap();
+        assert_eq!(value["idList"], "l1");
+        assert_eq!(value["idCardSource"], "c1");
+        assert_eq!(value["keepFromSource"], "all");
+        assert_eq!(value["pos"], "bottom");
+    }
+
+    #[test]
+    fn update_card_cover_serializes_color() {
+        let body = UpdateCardCover {
+            cover: CardCover {
+                color: Some("red".to_string()),
+            },
+        };
+
+        let value = serde_json::to_value(body).unwrap();
+        assert_eq!(value["cover"]["color"], "red");
+    }
+
+    #[test]
+    fn update_card_cover_serializes_null_to_clear() {
+        let body = UpdateCardCover {
+            cover: CardCover { color: None },
+        };
+
+        let value = serde_json::to_value(body).unwrap();
+        assert!(value["cover"]["color"].is_null());
+    }
+
+    #[test]
+    fn invite_member_serializes_type() {
+        let body = InviteMember {
+            email: "alice@example.com".to_string(),
+            member_type: "observer".to_string(),
+        };
+
+        let value = serde_json::to_value(body).unwrap();
+        assert_eq!(value["email"], "alice@example.com");
+        assert_eq!(value["type"], "observer");
+        assert!(value.get("member_type").is_none());
+    }
+
+    #[test]
+    fn action_deserializes_card_and_list_refs() {
+        let json = r#"{
+            "id": "a1",
+            "type": "updateCard",
+            "date": "2024-01-15T10:30:00.000Z",
+            "data": {
+                "card": { "id": "c1", "name": "Fix bug", "idShort": 12 },
+                "list": { "id": "l1", "name": "Doing" },
+                "board": { "id": "b1", "name": "Work" }
+            },
+            "memberCreator": { "fullName": "Alice", "username": "alice" }
+        }"#;
+        let action: Action = serde_json::from_str(json).unwrap();
+        assert_eq!(action.data.text, "");
+        assert_eq!(action.data.card.as_ref().unwrap().name, "Fix bug");
+        assert_eq!(action.data.list.as_ref().unwrap().name, "Doing");
+
+        let value = serde_json::to_value(&action).unwrap();
+        assert_eq!(value["type"], "updateCard");
+        assert_eq!(value["memberCreator"]["username"], "alice");
+        assert_eq!(value["data"]["card"]["id"], "c1");
+    }
+
+    #[test]
+    fn webhook_deserializes_callback_url_and_model() {
+        let json = r#"{
+            "id": "w1",
+            "description": "CI sync",
+            "idModel": "c1",
+            "callbackURL": "https://example.com/hook",
+            "active": true,
+            "consecutiveFailures": 0
+        }"#;
+        let webhook: Webhook = serde_json::from_str(json).unwrap();
+        assert_eq!(webhook.id_model, "c1");
+        assert_eq!(webhook.callback_url, "https://example.com/hook");
+        assert!(webhook.active);
+
+        let value = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(value["callbackURL"], "https://example.com/hook");
+        assert_eq!(value["idModel"], "c1");
+    }
+
+    #[test]
+    fn update_board_closed_serializes_value() {
+        let value = serde_json::to_value(UpdateBoardClosed { value: true }).unwrap();
+        assert_eq!(value, serde_json::json!({ "value": true }));
     }
 }
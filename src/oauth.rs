@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use reqwest::blocking::Client;
+use sha1::Sha1;
+
+use crate::error::TrelloError;
+
+const REQUEST_TOKEN_URL: &str = "https://trello.com/1/OAuthGetRequestToken";
+const AUTHORIZE_URL: &str = "https://trello.com/1/OAuthAuthorizeToken";
+const ACCESS_TOKEN_URL: &str = "https://trello.com/1/OAuthGetAccessToken";
+
+/// An OAuth token and its secret, as returned by the token endpoints.
+#[derive(Debug, PartialEq)]
+pub struct TokenPair {
+    pub token: String,
+    pub secret: String,
+}
+
+/// Runs Trello's OAuth 1.0a out-of-band flow: the user authorizes in a browser
+/// and pastes back a verifier PIN, which is exchanged for an access token.
+pub struct OAuthClient {
+    client: Client,
+    consumer_key: String,
+    consumer_secret: String,
+}
+
+impl OAuthClient {
+    pub fn new(consumer_key: &str, consumer_secret: &str) -> Self {
+        Self {
+            client: Client::new(),
+            consumer_key: consumer_key.to_string(),
+            consumer_secret: consumer_secret.to_string(),
+        }
+    }
+
+    pub fn get_request_token(&self) -> Result<TokenPair> {
+        self.token_request(REQUEST_TOKEN_URL, &[("oauth_callback", "oob")], None)
+            .context("Failed to obtain OAuth request token")
+    }
+
+    /// URL where the user grants this application access to their account.
+    pub fn authorize_url(&self, request_token: &TokenPair, app_name: &str) -> String {
+        format!(
+            "{}?oauth_token={}&name={}&scope=read,write&expiration=never",
+            AUTHORIZE_URL,
+            percent_encode(&request_token.token),
+            percent_encode(app_name)
+        )
+    }
+
+    pub fn get_access_token(&self, request_token: &TokenPair, verifier: &str) -> Result<TokenPair> {
+        self.token_request(
+            ACCESS_TOKEN_URL,
+            &[
+                ("oauth_token", &request_token.token),
+                ("oauth_verifier", verifier),
+            ],
+            Some(&request_token.secret),
+        )
+        .context("Failed to exchange verifier for OAuth access token")
+    }
+
+    fn token_request(
+        &self,
+        url: &str,
+        extra: &[(&str, &str)],
+        token_secret: Option<&str>,
+    ) -> Result<TokenPair> {
+        let header = self.authorization_header("POST", url, extra, token_secret);
+        let response = self
+            .client
+            .post(url)
+            .header("Authorization", header)
+            .send()
+            .context("Failed to send OAuth request")?;
+
+        if !response.status().is_success() {
+            return Err(TrelloError::from_http_response(response).into());
+        }
+        let body = response.text().context("Failed to read OAuth response")?;
+        parse_token_response(&body)
+    }
+
+    fn authorization_header(
+        &self,
+        method: &str,
+        url: &str,
+        extra: &[(&str, &str)],
+        token_secret: Option<&str>,
+    ) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let nonce = format!("{:x}{:x}", timestamp.as_nanos(), std::process::id());
+        let timestamp = timestamp.as_secs().to_string();
+
+        let mut params: BTreeMap<&str, &str> = BTreeMap::new();
+        params.insert("oauth_consumer_key", &self.consumer_key);
+        params.insert("oauth_nonce", &nonce);
+        params.insert("oauth_signature_method", "HMAC-SHA1");
+        params.insert("oauth_timestamp", &timestamp);
+        params.insert("oauth_version", "1.0");
+        params.extend(extra.iter().copied());
+
+        let signature = sign(
+            method,
+            url,
+            &params,
+            &self.consumer_secret,
+            token_secret.unwrap_or_default(),
+        );
+        params.insert("oauth_signature", &signature);
+
+        let fields: Vec<String> = params
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", percent_encode(k), percent_encode(v)))
+            .collect();
+        format!("OAuth {}", fields.join(", "))
+    }
+}
+
+/// HMAC-SHA1 signature over the OAuth signature base string (RFC 5849 §3.4).
+fn sign(
+    method: &str,
+    url: &str,
+    params: &BTreeMap<&str, &str>,
+    consumer_secret: &str,
+    token_secret: &str,
+) -> String {
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .map(|(k, v)| (percent_encode(k), percent_encode(v)))
+        .collect();
+    encoded.sort();
+    let param_string = encoded
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let base_string = format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        percent_encode(url),
+        percent_encode(&param_string)
+    );
+    let key = format!(
+        "{}&{}",
+        percent_encode(consumer_secret),
+        percent_encode(token_secret)
+    );
+
+    let mut mac =
+        Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(base_string.as_bytes());
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+/// Parse a form-encoded `oauth_token=...&oauth_token_secret=...` response.
+fn parse_token_response(body: &str) -> Result<TokenPair> {
+    let mut token = None;
+    let mut secret = None;
+    for (k, v) in reqwest::Url::parse(&format!("http://localhost/?{}", body.trim()))
+        .context("Failed to parse OAuth response")?
+        .query_pairs()
+    {
+        match k.as_ref() {
+            "oauth_token" => token = Some(v.into_owned()),
+            "oauth_token_secret" => secret = Some(v.into_owned()),
+            _ => {}
+        }
+    }
+    match (token, secret) {
+        (Some(token), Some(secret)) => Ok(TokenPair { token, secret }),
+        _ => anyhow::bail!("OAuth response is missing the token or secret: {}", body),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_encode_leaves_only_unreserved_characters() {
+        assert_eq!(percent_encode("abc-._~XYZ019"), "abc-._~XYZ019");
+        assert_eq!(
+            percent_encode("Ladies + Gentlemen!"),
+            "Ladies%20%2B%20Gentlemen%21"
+        );
+        assert_eq!(percent_encode("a=b&c"), "a%3Db%26c");
+        assert_eq!(percent_encode("☃"), "%E2%98%83");
+    }
+
+    #[test]
+    fn sign_matches_reference_signature() {
+        // Worked example from Twitter's "Creating a signature" documentation
+        let params: BTreeMap<&str, &str> = [
+            (
+                "status",
+                "Hello Ladies + Gentlemen, a signed OAuth request!",
+            ),
+            ("include_entities", "true"),
+            ("oauth_consumer_key", "xvz1evFS4wEEPTGEFPHBog"),
+            ("oauth_nonce", "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "1318622958"),
+            (
+                "oauth_token",
+                "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+            ),
+            ("oauth_version", "1.0"),
+        ]
+        .into_iter()
+        .collect();
+
+        let signature = sign(
+            "POST",
+            "https://api.twitter.com/1.1/statuses/update.json",
+            &params,
+            "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+            "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+        );
+        assert_eq!(signature, "hCtSmYh+iHYCEqBWrE7C7hYmtUk=");
+    }
+
+    #[test]
+    fn authorization_header_includes_signed_oauth_fields() {
+        let client = OAuthClient::new("key", "secret");
+        let header = client.authorization_header(
+            "POST",
+            REQUEST_TOKEN_URL,
+            &[("oauth_callback", "oob")],
+            None,
+        );
+        assert!(header.starts_with("OAuth "));
+        for field in [
+            "oauth_callback=\"oob\"",
+            "oauth_consumer_key=\"key\"",
+            "oauth_signature_method=\"HMAC-SHA1\"",
+            "oauth_version=\"1.0\"",
+            "oauth_nonce=",
+            "oauth_timestamp=",
+            "oauth_signature=",
+        ] {
+            assert!(header.contains(field), "missing {} in {}", field, header);
+        }
+    }
+
+    #[test]
+    fn authorize_url_encodes_token_and_name() {
+        let client = OAuthClient::new("key", "secret");
+        let token = TokenPair {
+            token: "abc123".to_string(),
+            secret: "s".to_string(),
+        };
+        assert_eq!(
+            client.authorize_url(&token, "trello cli"),
+            "https://trello.com/1/OAuthAuthorizeToken?oauth_token=abc123&name=trello%20cli&scope=read,write&expiration=never"
+        );
+    }
+
+    #[test]
+    fn parse_token_response_extracts_token_and_secret() {
+        let pair = parse_token_response(
+            "oauth_token=tok&oauth_token_secret=sec%3D&oauth_callback_confirmed=true",
+        )
+        .unwrap();
+        assert_eq!(
+            pair,
+            TokenPair {
+                token: "tok".to_string(),
+                secret: "sec=".to_string(),
+            }
+        );
+
+        assert!(parse_token_response("oauth_token=tok").is_err());
+    }
+}
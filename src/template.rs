@@ -0,0 +1,322 @@
+use anyhow::Result;
+
+use crate::CardResult;
+use crate::error::TrelloError;
+
+/// Card fields a template can print, e.g. `{{ card.title }}`.
+const CARD_FIELDS: [&str; 7] = [
+    "id",
+    "board",
+    "list",
+    "title",
+    "url",
+    "labels",
+    "list_closed",
+];
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    /// The inside of `{{ ... }}`
+    Expr(String),
+    /// The inside of `{% ... %}`
+    Tag(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    /// `{{ var.field }}`
+    Field {
+        var: String,
+        field: String,
+    },
+    /// `{% for var in cards %} ... {% endfor %}`
+    For {
+        var: String,
+        body: Vec<Node>,
+    },
+}
+
+/// A minimal Jinja-like template for `card find` output. Supports `{{ card.<field> }}`
+/// substitutions inside `{% for card in cards %}...{% endfor %}` loops, and nothing else.
+///
+/// As with Jinja's `trim_blocks`, a newline directly after a `{% ... %}` tag is dropped,
+/// so tags on lines of their own don't leave blank lines in the output.
+#[derive(Debug)]
+pub struct TemplateEngine {
+    nodes: Vec<Node>,
+}
+
+impl TemplateEngine {
+    /// Parse `template`, checking that every variable is bound by a loop and names a
+    /// known card field.
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut tokens = tokenize(template)?.into_iter();
+        let nodes = parse_block(&mut tokens, &mut Vec::new(), false)?;
+        Ok(Self { nodes })
+    }
+
+    /// Render the template with `cards` as the `cards` variable.
+    pub fn render(&self, cards: &[CardResult]) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, cards, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+fn template_error(message: String) -> anyhow::Error {
+    TrelloError::User(format!("Invalid output template: {}", message)).into()
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    loop {
+        let start = match (rest.find("{{"), rest.find("{%")) {
+            (Some(a), Some(b)) => a.min(b),
+            (Some(a), None) | (None, Some(a)) => a,
+            (None, None) => break,
+        };
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+        let is_tag = rest[start..].starts_with("{%");
+        let close = if is_tag { "%}" } else { "}}" };
+        let body_start = start + 2;
+        let Some(len) = rest[body_start..].find(close) else {
+            return Err(template_error(format!(
+                "'{}' is never closed with '{}'",
+                &rest[start..body_start],
+                close
+            )));
+        };
+        let inner = rest[body_start..body_start + len].trim().to_string();
+        rest = &rest[body_start + len + 2..];
+        if is_tag {
+            tokens.push(Token::Tag(inner));
+            rest = rest
+                .strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+                .unwrap_or(rest);
+        } else {
+            tokens.push(Token::Expr(inner));
+        }
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+/// Parse nodes up to the end of the template, or up to the `endfor` closing the
+/// enclosing loop when `in_loop` is set. `scope` holds the bound loop variables.
+fn parse_block(
+    tokens: &mut impl Iterator<Item = Token>,
+    scope: &mut Vec<String>,
+    in_loop: bool,
+) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Expr(expr) => {
+                let Some((var, field)) = expr.split_once('.') else {
+                    return Err(template_error(format!(
+                        "'{{{{ {} }}}}' should name a card field, e.g. '{{{{ card.title }}}}'",
+                        expr
+                    )));
+                };
+                let (var, field) = (var.trim(), field.trim());
+                if !scope.iter().any(|v| v == var) {
+                    return Err(template_error(format!(
+                        "'{}' is not a loop variable; use it inside '{{% for {} in cards %}}'",
+                        var, var
+                    )));
+                }
+                if !CARD_FIELDS.contains(&field) {
+                    return Err(template_error(format!(
+                        "unknown card field '{}' (fields: {})",
+                        field,
+                        CARD_FIELDS.join(", ")
+                    )));
+                }
+                nodes.push(Node::Field {
+                    var: var.to_string(),
+                    field: field.to_string(),
+                });
+            }
+            Token::Tag(tag) => {
+                let words: Vec<&str> = tag.split_whitespace().collect();
+                match words[..] {
+                    ["for", var, "in", "cards"] => {
+                        scope.push(var.to_string());
+                        let body = parse_block(tokens, scope, true)?;
+                        scope.pop();
+                        nodes.push(Node::For {
+                            var: var.to_string(),
+                            body,
+                        });
+                    }
+                    ["for", ..] => {
+                        return Err(template_error(format!(
+                            "'{{% {} %}}' should be '{{% for <name> in cards %}}'",
+                            tag
+                        )));
+                    }
+                    ["endfor"] if in_loop => return Ok(nodes),
+                    ["endfor"] => {
+                        return Err(template_error(
+                            "'{% endfor %}' without a matching '{% for %}'".to_string(),
+                        ));
+                    }
+                    _ => {
+                        return Err(template_error(format!(
+                            "unsupported tag '{{% {} %}}'; only 'for' loops are supported",
+                            tag
+                        )));
+                    }
+                }
+            }
+        }
+    }
+    if in_loop {
+        return Err(template_error("missing '{% endfor %}'".to_string()));
+    }
+    Ok(nodes)
+}
+
+/// Append `nodes` rendered to `out`. `scope` maps loop variables to their current card.
+fn render_nodes<'n, 'c>(
+    nodes: &'n [Node],
+    cards: &'c [CardResult],
+    scope: &mut Vec<(&'n str, &'c CardResult)>,
+    out: &mut String,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Field { var, field } => {
+                // Parsing guarantees the variable is bound
+                if let Some((_, card)) = scope.iter().rev().find(|(name, _)| name == var) {
+                    out.push_str(&card_field(card, field));
+                }
+            }
+            Node::For { var, body } => {
+                for card in cards {
+                    scope.push((var, card));
+                    render_nodes(body, cards, scope, out);
+                    scope.pop();
+                }
+            }
+        }
+    }
+}
+
+fn card_field(card: &CardResult, field: &str) -> String {
+    match field {
+        "id" => card.id.clone(),
+        "board" => card.board.clone(),
+        "list" => card.list.clone(),
+        "title" => card.title.clone(),
+        "url" => card.url.clone().unwrap_or_default(),
+        "labels" => card.labels.as_deref().unwrap_or_default().join(", "),
+        "list_closed" => card.list_closed.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(id: &str, title: &str, labels: Option<&[&str]>) -> CardResult {
+        CardResult {
+            id: id.to_string(),
+            board: "Work".to_string(),
+            list: "Doing".to_string(),
+            title: title.to_string(),
+            board_id: "board1".to_string(),
+            list_id: "list1".to_string(),
+            closed: false,
+            list_closed: false,
+            pos: None,
+            labels: labels.map(|l| l.iter().map(|s| s.to_string()).collect()),
+            url: Some(format!("https://trello.com/c/{}", id)),
+            description: None,
+        }
+    }
+
+    fn render(template: &str, cards: &[CardResult]) -> String {
+        TemplateEngine::parse(template).unwrap().render(cards)
+    }
+
+    #[test]
+    fn renders_markdown_table() {
+        let cards = [
+            card("c1", "Fix bug", Some(&["Bug", "Urgent"])),
+            card("c2", "Write docs", None),
+        ];
+        let template = "| Card | List | Labels |\n\
+                        | --- | --- | --- |\n\
+                        {% for card in cards %}\n\
+                        | [{{ card.title }}]({{card.url}}) | {{ card.list }} | {{ card.labels }} |\n\
+                        {% endfor %}\n\
+                        { is copied through";
+        assert_eq!(
+            render(template, &cards),
+            "| Card | List | Labels |\n\
+             | --- | --- | --- |\n\
+             | [Fix bug](https://trello.com/c/c1) | Doing | Bug, Urgent |\n\
+             | [Write docs](https://trello.com/c/c2) | Doing |  |\n\
+             { is copied through"
+        );
+    }
+
+    #[test]
+    fn renders_nested_loops_and_empty_results() {
+        let cards = [card("c1", "A", None), card("c2", "B", None)];
+        let template = "{% for a in cards %}{% for b in cards %}{{ a.title }}{{ b.title }} {% endfor %}{% endfor %}";
+        assert_eq!(render(template, &cards), "AA AB BA BB ");
+        assert_eq!(
+            render("Cards:\n{% for c in cards %}{{ c.id }}\n{% endfor %}", &[]),
+            "Cards:\n"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        let cases = [
+            ("{{ card.title }}", "not a loop variable"),
+            (
+                "{% for card in cards %}{{ card.name }}{% endfor %}",
+                "unknown card field 'name'",
+            ),
+            (
+                "{% for card in cards %}{{ title }}{% endfor %}",
+                "should name a card field",
+            ),
+            ("{% for card in cards %}{{ card.title }", "never closed"),
+            ("{% for card in cards %}", "missing '{% endfor %}'"),
+            ("{% endfor %}", "without a matching"),
+            (
+                "{% for card in boards %}{% endfor %}",
+                "for <name> in cards",
+            ),
+            ("{% if card.closed %}", "unsupported tag"),
+        ];
+        for (template, expected) in cases {
+            let err = TemplateEngine::parse(template).unwrap_err();
+            assert!(
+                err.to_string().contains(expected),
+                "{}: got {}",
+                template,
+                err
+            );
+            assert_eq!(
+                crate::error::exit_code(&err),
+                crate::error::ExitCode::UserError
+            );
+        }
+    }
+}